@@ -1,8 +1,11 @@
 use crate::handlers::{
-    admin_score_upload, cluster_stats, commissions, config, docs, glossary, list_validators,
-    reports_commission_changes, reports_scoring, reports_scoring_html, reports_staking,
-    unstake_hints, uptimes, validator_score_breakdown, validator_scores, validators_flat, versions,
-    workflow_metrics_upload,
+    admin_blacklist, admin_blacklist_add, admin_blacklist_audit, admin_blacklist_proposal_review,
+    admin_blacklist_proposals, admin_blacklist_remove, admin_score_upload, cluster_stats,
+    commissions, config, docs, events, glossary, list_validators, mev, reports_commission_changes,
+    reports_scoring, reports_scoring_diff, reports_scoring_html, reports_staking, scores_what_if,
+    scoring_run_score_breakdown, scoring_run_scores, scoring_runs, unstake_hints,
+    unstake_hints_history, uptimes, validator_score_breakdown, validator_scores, validators_flat,
+    versions, workflow_metrics_upload,
 };
 use utoipa::OpenApi;
 
@@ -17,6 +20,14 @@ use utoipa::OpenApi;
         )
     ),
     components(
+        schemas(admin_blacklist::ResponseAdminBlacklist),
+        schemas(admin_blacklist_add::RequestAdminBlacklistAdd),
+        schemas(admin_blacklist_add::ResponseAdminBlacklistAdd),
+        schemas(admin_blacklist_audit::ResponseAdminBlacklistAudit),
+        schemas(admin_blacklist_proposal_review::ProposalDecision),
+        schemas(admin_blacklist_proposal_review::ResponseAdminBlacklistProposalReview),
+        schemas(admin_blacklist_proposals::ResponseAdminBlacklistProposals),
+        schemas(admin_blacklist_remove::ResponseAdminBlacklistRemove),
         schemas(admin_score_upload::ResponseAdminScoreUpload),
         schemas(cluster_stats::ResponseClusterStats),
         schemas(commissions::ResponseCommissions),
@@ -24,24 +35,62 @@ use utoipa::OpenApi;
         schemas(config::ResponseConfig),
         schemas(config::StakeDelegationAuthorityRecord),
         schemas(list_validators::ResponseValidators),
+        schemas(mev::ResponseMev),
         schemas(reports_commission_changes::CommissionChange),
         schemas(reports_commission_changes::ResponseCommissionChanges),
         schemas(reports_scoring::ResponseReportScoring),
+        schemas(reports_scoring_diff::ReportFormat),
+        schemas(reports_scoring_diff::ResponseReportScoringDiff),
         schemas(reports_staking::ResponseReportStaking),
         schemas(reports_staking::Stake),
+        schemas(scores_what_if::RequestScoresWhatIf),
+        schemas(scores_what_if::ResponseScoresWhatIf),
+        schemas(scoring_run_scores::ResponseScoringRunScores),
+        schemas(scoring_runs::ResponseScoringRuns),
+        schemas(scoring_runs::ScoringRun),
+        schemas(store::dto::BlacklistAuditRecord),
+        schemas(store::dto::BlacklistEntry),
+        schemas(store::dto::BlacklistProposalRecord),
+        schemas(store::dto::BlacklistRule),
         schemas(store::dto::BlockProductionStats),
         schemas(store::dto::ClusterStats),
         schemas(store::dto::CommissionRecord),
+        schemas(store::dto::ConcentrationChange),
         schemas(store::dto::DCConcentrationStats),
+        schemas(store::dto::DecentralizationBreakdown),
+        schemas(store::dto::DecentralizationChange),
+        schemas(store::dto::DecentralizationImpact),
+        schemas(store::dto::DecentralizationIndices),
+        schemas(store::dto::DecentralizationIndicesChange),
+        schemas(store::dto::DecentralizationStats),
+        schemas(store::dto::HighCommissionRule),
+        schemas(store::dto::HighMevCommissionRule),
+        schemas(store::dto::LowCreditsRule),
+        schemas(store::dto::LowUptimeRule),
+        schemas(store::dto::MevRecord),
+        schemas(store::dto::OutdatedVersionRule),
+        schemas(store::dto::ScoringConfig),
+        schemas(store::dto::ScoringEligibility),
+        schemas(store::dto::ScoringWeights),
+        schemas(store::dto::StakeControlSplit),
+        schemas(store::dto::StakeEligibility),
+        schemas(store::dto::SuperminorityRule),
+        schemas(store::dto::UnstakeHintHistoryRecord),
         schemas(store::dto::UnstakeHintRecord),
+        schemas(store::dto::UnstakeHintsConfig),
         schemas(store::dto::UptimeRecord),
         schemas(store::dto::ValidatorEpochStats),
+        schemas(store::dto::ValidatorEventRecord),
+        schemas(store::dto::ValidatorEventType),
         schemas(store::dto::ValidatorRecord),
+        schemas(store::dto::ValidatorScoreDiff),
         schemas(store::dto::ValidatorsAggregated),
         schemas(store::dto::ValidatorScoreRecord),
         schemas(store::dto::ValidatorWarning),
         schemas(store::dto::VersionRecord),
+        schemas(store::scoring_engine::StakeControl),
         schemas(unstake_hints::ResponseUnstakeHints),
+        schemas(unstake_hints_history::ResponseUnstakeHintsHistory),
         schemas(uptimes::ResponseUptimes),
         schemas(validator_score_breakdown::ResponseScoreBreakdown),
         schemas(validator_score_breakdown::ScoreBreakdown),
@@ -50,18 +99,32 @@ use utoipa::OpenApi;
         schemas(workflow_metrics_upload::ResponseAdminWorkflowMetrics),
     ),
     paths(
+        admin_blacklist::handler,
+        admin_blacklist_add::handler,
+        admin_blacklist_audit::handler,
+        admin_blacklist_proposal_review::handler,
+        admin_blacklist_proposals::handler,
+        admin_blacklist_remove::handler,
         admin_score_upload::handler,
         cluster_stats::handler,
         commissions::handler,
         config::handler,
         docs::handler,
+        events::handler,
         glossary::handler,
         list_validators::handler,
+        mev::handler,
         reports_commission_changes::handler,
         reports_scoring_html::handler,
         reports_scoring::handler,
+        reports_scoring_diff::handler,
         reports_staking::handler,
+        scores_what_if::handler,
+        scoring_run_score_breakdown::handler,
+        scoring_run_scores::handler,
+        scoring_runs::handler,
         unstake_hints::handler,
+        unstake_hints_history::handler,
         uptimes::handler,
         validator_score_breakdown::handler,
         validator_scores::handler,
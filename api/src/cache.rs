@@ -3,19 +3,23 @@ use log::{error, info};
 use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 use store::dto::{
-    ClusterStats, CommissionRecord, ScoringRunRecord, UptimeRecord, ValidatorRecord,
-    ValidatorScoreRecord, ValidatorsAggregated, VersionRecord,
+    ClusterStats, CommissionRecord, MevRecord, ScoringRunRecord, UptimeRecord,
+    ValidatorAggregatedFlat, ValidatorRecord, ValidatorScoreRecord, ValidatorsAggregated,
+    VersionRecord,
 };
 use tokio::time::{sleep, Duration};
 
 const DEFAULT_EPOCHS: u64 = 20;
+const DEFAULT_FLAT_EPOCHS: u64 = 10;
 
 type CachedValidators = HashMap<String, ValidatorRecord>;
 type CachedCommissions = HashMap<String, Vec<CommissionRecord>>;
 type CachedVersions = HashMap<String, Vec<VersionRecord>>;
 type CachedUptimes = HashMap<String, Vec<UptimeRecord>>;
+type CachedMev = HashMap<String, Vec<MevRecord>>;
 type CachedClusterStats = Option<ClusterStats>;
 type CachedValidatorsAggregated = Vec<ValidatorsAggregated>;
+type CachedValidatorsFlat = Vec<ValidatorAggregatedFlat>;
 
 #[derive(Default, Clone)]
 pub struct CachedScores {
@@ -29,8 +33,10 @@ pub struct Cache {
     pub commissions: CachedCommissions,
     pub versions: CachedVersions,
     pub uptimes: CachedUptimes,
+    pub mev: CachedMev,
     pub cluster_stats: CachedClusterStats,
     pub validators_aggregated: CachedValidatorsAggregated,
+    pub validators_flat: CachedValidatorsFlat,
     pub validators_scores: CachedScores,
 }
 
@@ -61,10 +67,18 @@ impl Cache {
         self.uptimes.get(vote_account).cloned()
     }
 
+    pub fn get_mev(&self, vote_account: &String) -> Option<Vec<MevRecord>> {
+        self.mev.get(vote_account).cloned()
+    }
+
     pub fn get_validators_aggregated(&self) -> CachedValidatorsAggregated {
         self.validators_aggregated.clone()
     }
 
+    pub fn get_validators_flat(&self) -> CachedValidatorsFlat {
+        self.validators_flat.clone()
+    }
+
     pub fn get_validators_scores(&self) -> CachedScores {
         self.validators_scores.clone()
     }
@@ -84,6 +98,12 @@ impl Cache {
                     .take(epochs)
                     .cloned()
                     .collect(),
+                decentralization_stats: cluster_stats
+                    .decentralization_stats
+                    .iter()
+                    .take(epochs)
+                    .cloned()
+                    .collect(),
             }),
             _ => None,
         }
@@ -148,6 +168,16 @@ pub async fn warm_uptimes_cache(context: &WrappedContext) -> anyhow::Result<()>
     Ok(())
 }
 
+pub async fn warm_mev_cache(context: &WrappedContext) -> anyhow::Result<()> {
+    info!("Loading MEV from DB");
+
+    let mev = store::utils::load_mev(&context.read().await.psql_client, DEFAULT_EPOCHS).await?;
+    context.write().await.cache.mev.clone_from(&mev);
+    info!("Loaded MEV to cache: {}", mev.len());
+
+    Ok(())
+}
+
 pub async fn warm_cluster_stats_cache(context: &WrappedContext) -> anyhow::Result<()> {
     info!("Loading cluster_stats from DB");
 
@@ -193,6 +223,38 @@ pub async fn warm_scores_cache(context: &WrappedContext) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub async fn warm_validators_flat_cache(context: &WrappedContext) -> anyhow::Result<()> {
+    info!("Loading flat validators from DB");
+
+    // Use the same data as the last scoring run if there is one
+    let scoring_run_epoch = context
+        .read()
+        .await
+        .cache
+        .validators_scores
+        .scoring_run
+        .as_ref()
+        .map(|scoring_run| scoring_run.epoch as u64);
+    let last_epoch = match scoring_run_epoch {
+        Some(epoch) => epoch,
+        None => match store::utils::get_last_epoch(&context.read().await.psql_client).await? {
+            Some(epoch) => epoch.saturating_sub(1),
+            None => return Ok(()),
+        },
+    };
+
+    let validators_flat = store::utils::load_validators_aggregated_flat(
+        &context.read().await.psql_client,
+        last_epoch,
+        DEFAULT_FLAT_EPOCHS,
+    )
+    .await?;
+    info!("Loaded flat validators to cache: {}", validators_flat.len());
+    context.write().await.cache.validators_flat = validators_flat;
+
+    Ok(())
+}
+
 pub fn spawn_cache_warmer(context: WrappedContext) {
     tokio::spawn(async move {
         loop {
@@ -202,6 +264,10 @@ pub fn spawn_cache_warmer(context: WrappedContext) {
                 error!("Failed to update the scores: {}", err);
             }
 
+            if let Err(err) = warm_validators_flat_cache(&context).await {
+                error!("Failed to update the flat validators: {}", err);
+            }
+
             if let Err(err) = warm_versions_cache(&context).await {
                 error!("Failed to update the versions: {}", err);
             }
@@ -214,6 +280,10 @@ pub fn spawn_cache_warmer(context: WrappedContext) {
                 error!("Failed to update the uptimes: {}", err);
             }
 
+            if let Err(err) = warm_mev_cache(&context).await {
+                error!("Failed to update the MEV: {}", err);
+            }
+
             if let Err(err) = warm_cluster_stats_cache(&context).await {
                 error!("Failed to update the cluster stats: {}", err);
             }
@@ -1,26 +1,35 @@
 use crate::cache::Cache;
+use crate::event_stream::{StreamEvent, EVENTS_CHANNEL_CAPACITY};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use store::dto::UnstakeHintsConfig;
+use tokio::sync::{broadcast, RwLock};
 use tokio_postgres::Client;
 
 pub struct Context {
     pub psql_client: Client,
     pub glossary_path: String,
-    pub blacklist_path: String,
+    pub msol_votes_path: Option<String>,
+    pub unstake_hints_config: UnstakeHintsConfig,
     pub cache: Cache,
+    pub events: broadcast::Sender<StreamEvent>,
 }
 
 impl Context {
     pub fn new(
         psql_client: Client,
         glossary_path: String,
-        blacklist_path: String,
+        msol_votes_path: Option<String>,
+        unstake_hints_config: UnstakeHintsConfig,
     ) -> anyhow::Result<Self> {
+        let (events, _) = broadcast::channel(EVENTS_CHANNEL_CAPACITY);
+
         Ok(Self {
             psql_client,
             glossary_path,
-            blacklist_path,
+            msol_votes_path,
+            unstake_hints_config,
             cache: Cache::new(),
+            events,
         })
     }
 }
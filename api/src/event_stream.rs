@@ -0,0 +1,122 @@
+use crate::context::WrappedContext;
+use crate::handlers::scoring_runs::ScoringRun;
+use log::{error, info};
+use rust_decimal::prelude::ToPrimitive;
+use store::dto::ValidatorEventRecord;
+use tokio::sync::broadcast;
+use tokio::time::{sleep, Duration};
+
+pub const EVENTS_CHANNEL_CAPACITY: usize = 1000;
+const EVENTS_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug)]
+pub enum StreamEvent {
+    Validator(ValidatorEventRecord),
+    ScoringRun(ScoringRun),
+}
+
+impl StreamEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StreamEvent::Validator(_) => "validator_event",
+            StreamEvent::ScoringRun(_) => "scoring_run",
+        }
+    }
+
+    pub fn id(&self) -> String {
+        match self {
+            StreamEvent::Validator(event) => {
+                format!("validator_event-{}", event.validator_event_id)
+            }
+            StreamEvent::ScoringRun(scoring_run) => {
+                format!("scoring_run-{}", scoring_run.scoring_run_id)
+            }
+        }
+    }
+
+    pub fn vote_account(&self) -> Option<&String> {
+        match self {
+            StreamEvent::Validator(event) => Some(&event.vote_account),
+            StreamEvent::ScoringRun(_) => None,
+        }
+    }
+}
+
+struct LastSeenIds {
+    validator_event_id: i64,
+    scoring_run_id: i64,
+}
+
+async fn load_last_seen_ids(context: &WrappedContext) -> anyhow::Result<LastSeenIds> {
+    let psql_client = &context.read().await.psql_client;
+    let validator_event_id =
+        store::notifications::load_last_validator_event_id(psql_client).await?;
+    let scoring_run_id = store::utils::load_last_scoring_run(psql_client)
+        .await?
+        .and_then(|scoring_run| scoring_run.scoring_run_id.to_i64())
+        .unwrap_or_default();
+
+    Ok(LastSeenIds {
+        validator_event_id,
+        scoring_run_id,
+    })
+}
+
+async fn poll_events(
+    context: &WrappedContext,
+    last_seen_ids: &mut LastSeenIds,
+) -> anyhow::Result<Vec<StreamEvent>> {
+    let psql_client = &context.read().await.psql_client;
+    let mut events = vec![];
+
+    for event in store::notifications::load_validator_events_after(
+        psql_client,
+        last_seen_ids.validator_event_id,
+    )
+    .await?
+    {
+        last_seen_ids.validator_event_id = event.validator_event_id;
+        events.push(StreamEvent::Validator(event));
+    }
+
+    for scoring_run in
+        store::scoring::load_scoring_runs_after(psql_client, last_seen_ids.scoring_run_id).await?
+    {
+        let scoring_run = ScoringRun::from(scoring_run);
+        last_seen_ids.scoring_run_id = scoring_run.scoring_run_id;
+        events.push(StreamEvent::ScoringRun(scoring_run));
+    }
+
+    Ok(events)
+}
+
+/// Streams only the changes stored after the API started, clients are expected to load
+/// the current state from the regular endpoints.
+pub fn spawn_events_poller(context: WrappedContext, sender: broadcast::Sender<StreamEvent>) {
+    tokio::spawn(async move {
+        let mut last_seen_ids = loop {
+            match load_last_seen_ids(&context).await {
+                Ok(last_seen_ids) => break last_seen_ids,
+                Err(err) => error!("Failed to load the last seen events: {}", err),
+            }
+            sleep(EVENTS_POLL_INTERVAL).await;
+        };
+
+        loop {
+            sleep(EVENTS_POLL_INTERVAL).await;
+
+            match poll_events(&context, &mut last_seen_ids).await {
+                Ok(events) => {
+                    if !events.is_empty() {
+                        info!("Broadcasting {} new events", events.len());
+                    }
+                    for event in events {
+                        // Fails only when there are no subscribers
+                        let _ = sender.send(event);
+                    }
+                }
+                Err(err) => error!("Failed to poll new events: {}", err),
+            }
+        }
+    });
+}
@@ -0,0 +1,59 @@
+use crate::metrics;
+use crate::utils::response_error;
+use crate::{context::WrappedContext, utils::response_error_500};
+use serde::{Deserialize, Serialize};
+use store::dto::BlacklistEntry;
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseAdminBlacklist {
+    blacklist: Vec<BlacklistEntry>,
+}
+
+#[derive(Deserialize, Serialize, Debug, utoipa::IntoParams)]
+pub struct QueryParams {
+    include_expired: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    tag = "Admin",
+    operation_id = "List blacklist entries",
+    path = "/admin/blacklist",
+    params(QueryParams),
+    responses(
+        (status = 200, body = ResponseAdminBlacklist)
+    )
+)]
+pub async fn handler(
+    logged_in: bool,
+    query_params: QueryParams,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    metrics::REQUEST_ADMIN_BLACKLIST.inc();
+    log::info!("Fetching the blacklist {:?}", query_params);
+
+    if !logged_in {
+        log::error!("Unauthorized access!");
+        return Ok(response_error(
+            StatusCode::UNAUTHORIZED,
+            "Not authorized!".into(),
+        ));
+    }
+
+    let blacklist = store::blacklist::load_blacklist_entries(
+        &context.read().await.psql_client,
+        query_params.include_expired.unwrap_or(false),
+    )
+    .await;
+
+    Ok(match blacklist {
+        Ok(blacklist) => {
+            warp::reply::with_status(json(&ResponseAdminBlacklist { blacklist }), StatusCode::OK)
+        }
+        Err(err) => {
+            log::error!("Failed to load the blacklist: {}", err);
+            response_error_500("Failed to load the blacklist!".into())
+        }
+    })
+}
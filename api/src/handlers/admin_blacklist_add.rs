@@ -0,0 +1,75 @@
+use crate::metrics;
+use crate::utils::response_error;
+use crate::{context::WrappedContext, utils::response_error_500};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use store::dto::{BlacklistEntry, BlacklistRecord};
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Deserialize, Serialize, Debug, utoipa::ToSchema)]
+pub struct RequestAdminBlacklistAdd {
+    vote_account: String,
+    code: String,
+    evidence: Option<String>,
+    expires_at: Option<DateTime<Utc>>,
+    comment: Option<String>,
+}
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseAdminBlacklistAdd {
+    entry: BlacklistEntry,
+}
+
+#[utoipa::path(
+    post,
+    tag = "Admin",
+    operation_id = "Add blacklist entry",
+    path = "/admin/blacklist",
+    request_body = RequestAdminBlacklistAdd,
+    responses(
+        (status = 200, body = ResponseAdminBlacklistAdd)
+    )
+)]
+pub async fn handler(
+    logged_in: bool,
+    request: RequestAdminBlacklistAdd,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    metrics::REQUEST_ADMIN_BLACKLIST_ADD.inc();
+    log::info!("Adding blacklist entry {:?}", request);
+
+    if !logged_in {
+        log::error!("Unauthorized access!");
+        return Ok(response_error(
+            StatusCode::UNAUTHORIZED,
+            "Not authorized!".into(),
+        ));
+    }
+
+    if let Err(err) = store::blacklist::validate_blacklist_code(&request.code) {
+        return Ok(response_error(StatusCode::BAD_REQUEST, err.to_string()));
+    }
+
+    let record = BlacklistRecord {
+        vote_account: request.vote_account,
+        code: request.code,
+        evidence: request.evidence,
+        expires_at: request.expires_at,
+    };
+    let entry = store::blacklist::add_blacklist_entry(
+        &mut context.write().await.psql_client,
+        &record,
+        request.comment,
+    )
+    .await;
+
+    Ok(match entry {
+        Ok(entry) => {
+            warp::reply::with_status(json(&ResponseAdminBlacklistAdd { entry }), StatusCode::OK)
+        }
+        Err(err) => {
+            log::error!("Failed to add the blacklist entry: {}", err);
+            response_error_500("Failed to add the blacklist entry!".into())
+        }
+    })
+}
@@ -0,0 +1,59 @@
+use crate::metrics;
+use crate::utils::response_error;
+use crate::{context::WrappedContext, utils::response_error_500};
+use serde::{Deserialize, Serialize};
+use store::dto::BlacklistAuditRecord;
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseAdminBlacklistAudit {
+    audit: Vec<BlacklistAuditRecord>,
+}
+
+#[derive(Deserialize, Serialize, Debug, utoipa::IntoParams)]
+pub struct QueryParams {
+    query_vote_account: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    tag = "Admin",
+    operation_id = "List blacklist audit trail",
+    path = "/admin/blacklist/audit",
+    params(QueryParams),
+    responses(
+        (status = 200, body = ResponseAdminBlacklistAudit)
+    )
+)]
+pub async fn handler(
+    logged_in: bool,
+    query_params: QueryParams,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    metrics::REQUEST_ADMIN_BLACKLIST_AUDIT.inc();
+    log::info!("Fetching the blacklist audit trail {:?}", query_params);
+
+    if !logged_in {
+        log::error!("Unauthorized access!");
+        return Ok(response_error(
+            StatusCode::UNAUTHORIZED,
+            "Not authorized!".into(),
+        ));
+    }
+
+    let audit = store::blacklist::load_blacklist_audit(
+        &context.read().await.psql_client,
+        query_params.query_vote_account,
+    )
+    .await;
+
+    Ok(match audit {
+        Ok(audit) => {
+            warp::reply::with_status(json(&ResponseAdminBlacklistAudit { audit }), StatusCode::OK)
+        }
+        Err(err) => {
+            log::error!("Failed to load the blacklist audit trail: {}", err);
+            response_error_500("Failed to load the blacklist audit trail!".into())
+        }
+    })
+}
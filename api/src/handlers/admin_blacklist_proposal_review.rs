@@ -0,0 +1,79 @@
+use crate::metrics;
+use crate::utils::response_error;
+use crate::{context::WrappedContext, utils::response_error_500};
+use serde::{Deserialize, Serialize};
+use store::dto::BlacklistProposalRecord;
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProposalDecision {
+    Accept,
+    Reject,
+}
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseAdminBlacklistProposalReview {
+    proposal: BlacklistProposalRecord,
+}
+
+#[derive(Deserialize, Serialize, Debug, utoipa::IntoParams)]
+pub struct QueryParams {
+    decision: ProposalDecision,
+    comment: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    tag = "Admin",
+    operation_id = "Review blacklist proposal",
+    path = "/admin/blacklist/proposals/<blacklist_proposal_id>",
+    params(QueryParams),
+    responses(
+        (status = 200, body = ResponseAdminBlacklistProposalReview)
+    )
+)]
+pub async fn handler(
+    blacklist_proposal_id: i64,
+    logged_in: bool,
+    query_params: QueryParams,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    metrics::REQUEST_ADMIN_BLACKLIST_PROPOSAL_REVIEW.inc();
+    log::info!(
+        "Reviewing blacklist proposal {} {:?}",
+        blacklist_proposal_id,
+        query_params
+    );
+
+    if !logged_in {
+        log::error!("Unauthorized access!");
+        return Ok(response_error(
+            StatusCode::UNAUTHORIZED,
+            "Not authorized!".into(),
+        ));
+    }
+
+    let proposal = store::blacklist::review_blacklist_proposal(
+        &mut context.write().await.psql_client,
+        blacklist_proposal_id,
+        query_params.decision == ProposalDecision::Accept,
+        query_params.comment,
+    )
+    .await;
+
+    Ok(match proposal {
+        Ok(Some(proposal)) => warp::reply::with_status(
+            json(&ResponseAdminBlacklistProposalReview { proposal }),
+            StatusCode::OK,
+        ),
+        Ok(None) => response_error(
+            StatusCode::NOT_FOUND,
+            "Pending blacklist proposal not found!".into(),
+        ),
+        Err(err) => {
+            log::error!("Failed to review the blacklist proposal: {}", err);
+            response_error_500("Failed to review the blacklist proposal!".into())
+        }
+    })
+}
@@ -0,0 +1,61 @@
+use crate::metrics;
+use crate::utils::response_error;
+use crate::{context::WrappedContext, utils::response_error_500};
+use serde::{Deserialize, Serialize};
+use store::dto::BlacklistProposalRecord;
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseAdminBlacklistProposals {
+    proposals: Vec<BlacklistProposalRecord>,
+}
+
+#[derive(Deserialize, Serialize, Debug, utoipa::IntoParams)]
+pub struct QueryParams {
+    /// `PENDING`, `ACCEPTED` or `REJECTED`, all proposals are listed if missing
+    status: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    tag = "Admin",
+    operation_id = "List blacklist proposals",
+    path = "/admin/blacklist/proposals",
+    params(QueryParams),
+    responses(
+        (status = 200, body = ResponseAdminBlacklistProposals)
+    )
+)]
+pub async fn handler(
+    logged_in: bool,
+    query_params: QueryParams,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    metrics::REQUEST_ADMIN_BLACKLIST_PROPOSALS.inc();
+    log::info!("Fetching blacklist proposals {:?}", query_params);
+
+    if !logged_in {
+        log::error!("Unauthorized access!");
+        return Ok(response_error(
+            StatusCode::UNAUTHORIZED,
+            "Not authorized!".into(),
+        ));
+    }
+
+    let proposals = store::blacklist::load_blacklist_proposals(
+        &context.read().await.psql_client,
+        query_params.status,
+    )
+    .await;
+
+    Ok(match proposals {
+        Ok(proposals) => warp::reply::with_status(
+            json(&ResponseAdminBlacklistProposals { proposals }),
+            StatusCode::OK,
+        ),
+        Err(err) => {
+            log::error!("Failed to load blacklist proposals: {}", err);
+            response_error_500("Failed to load blacklist proposals!".into())
+        }
+    })
+}
@@ -0,0 +1,71 @@
+use crate::metrics;
+use crate::utils::response_error;
+use crate::{context::WrappedContext, utils::response_error_500};
+use serde::{Deserialize, Serialize};
+use store::dto::BlacklistEntry;
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseAdminBlacklistRemove {
+    removed: Vec<BlacklistEntry>,
+}
+
+#[derive(Deserialize, Serialize, Debug, utoipa::IntoParams)]
+pub struct QueryParams {
+    code: Option<String>,
+    comment: Option<String>,
+}
+
+#[utoipa::path(
+    delete,
+    tag = "Admin",
+    operation_id = "Remove blacklist entries",
+    path = "/admin/blacklist/<vote_account>",
+    params(QueryParams),
+    responses(
+        (status = 200, body = ResponseAdminBlacklistRemove)
+    )
+)]
+pub async fn handler(
+    vote_account: String,
+    logged_in: bool,
+    query_params: QueryParams,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    metrics::REQUEST_ADMIN_BLACKLIST_REMOVE.inc();
+    log::info!(
+        "Removing blacklist entries of {} {:?}",
+        vote_account,
+        query_params
+    );
+
+    if !logged_in {
+        log::error!("Unauthorized access!");
+        return Ok(response_error(
+            StatusCode::UNAUTHORIZED,
+            "Not authorized!".into(),
+        ));
+    }
+
+    let removed = store::blacklist::remove_blacklist_entries(
+        &mut context.write().await.psql_client,
+        &vote_account,
+        &query_params.code,
+        query_params.comment,
+    )
+    .await;
+
+    Ok(match removed {
+        Ok(removed) if removed.is_empty() => {
+            response_error(StatusCode::NOT_FOUND, "Blacklist entry not found!".into())
+        }
+        Ok(removed) => warp::reply::with_status(
+            json(&ResponseAdminBlacklistRemove { removed }),
+            StatusCode::OK,
+        ),
+        Err(err) => {
+            log::error!("Failed to remove the blacklist entries: {}", err);
+            response_error_500("Failed to remove the blacklist entries!".into())
+        }
+    })
+}
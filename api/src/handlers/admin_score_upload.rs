@@ -13,6 +13,7 @@ use warp::{
 };
 
 const SCORES_CSV_PART_NAME: &str = "scores_csv";
+const CONFIG_YAML_PART_NAME: &str = "config_yaml";
 
 #[derive(Serialize, Debug, utoipa::ToSchema)]
 pub struct ResponseAdminScoreUpload {
@@ -27,6 +28,19 @@ pub struct QueryParams {
     ui_id: String,
 }
 
+async fn read_part(part: Part) -> Result<Vec<u8>, warp::Rejection> {
+    part.stream()
+        .try_fold(Vec::new(), |mut vec, data| {
+            vec.put(data);
+            async move { Ok(vec) }
+        })
+        .await
+        .map_err(|err| {
+            log::error!("Upload part reading error: {}", err);
+            warp::reject::reject()
+        })
+}
+
 #[utoipa::path(
     post,
     tag = "Admin",
@@ -66,12 +80,33 @@ pub async fn handler(
         .map(|weight| weight.parse::<f64>().unwrap())
         .collect();
 
-    let scores_csv_part = parts.into_iter().find_map(|part| {
-        if !part.name().eq(SCORES_CSV_PART_NAME) {
-            return None;
+    let (scores_csv_parts, config_yaml_parts): (Vec<_>, Vec<_>) = parts
+        .into_iter()
+        .filter(|part| {
+            part.name().eq(SCORES_CSV_PART_NAME) || part.name().eq(CONFIG_YAML_PART_NAME)
+        })
+        .partition(|part| part.name().eq(SCORES_CSV_PART_NAME));
+    let scores_csv_part = scores_csv_parts.into_iter().next();
+
+    let config = match config_yaml_parts.into_iter().next() {
+        Some(part) => {
+            let config_yaml = read_part(part).await?;
+            match serde_yaml::from_slice::<store::dto::ScoringConfig>(&config_yaml)
+                .map_err(anyhow::Error::from)
+                .and_then(|config| config.validate().map(|_| config))
+            {
+                Ok(config) => Some(config),
+                Err(err) => {
+                    log::error!("Failed to parse the scoring config: {}", err);
+                    return Ok(response_error(
+                        StatusCode::BAD_REQUEST,
+                        "Cannot parse the scoring config!".into(),
+                    ));
+                }
+            }
         }
-        return Some(part);
-    });
+        None => None,
+    };
 
     let scores_csv_part = match scores_csv_part {
         Some(part) => part,
@@ -84,17 +119,7 @@ pub async fn handler(
         }
     };
 
-    let scores_csv = scores_csv_part
-        .stream()
-        .try_fold(Vec::new(), |mut vec, data| {
-            vec.put(data);
-            async move { Ok(vec) }
-        })
-        .await
-        .map_err(|err| {
-            log::error!("CSV reading error: {}", err);
-            warp::reject::reject()
-        })?;
+    let scores_csv = read_part(scores_csv_part).await?;
 
     let mut rows_processed = 0;
     let mut validator_scores: Vec<store::dto::ValidatorScoringCsvRow> = Default::default();
@@ -121,6 +146,8 @@ pub async fn handler(
         query_params.ui_id,
         components,
         component_weights,
+        config.as_ref(),
+        None,
         validator_scores,
     )
     .await;
@@ -1,10 +1,12 @@
 use crate::context::WrappedContext;
 use serde::{Deserialize, Serialize};
+use store::dto::UnstakeHintsConfig;
 use warp::{http::StatusCode, reply, Reply};
 
 #[derive(Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ResponseConfig {
     stakes: ConfigStakes,
+    unstake_hints: UnstakeHintsConfig,
 }
 
 #[derive(Serialize, Deserialize, utoipa::ToSchema)]
@@ -27,8 +29,9 @@ pub struct StakeDelegationAuthorityRecord {
         (status = 200, body = ResponseConfig)
     )
 )]
-pub async fn handler(_context: WrappedContext) -> Result<impl Reply, warp::Rejection> {
+pub async fn handler(context: WrappedContext) -> Result<impl Reply, warp::Rejection> {
     log::info!("Serving the configuration data");
+    let unstake_hints = context.read().await.unstake_hints_config.clone();
     Ok(warp::reply::with_status(
         reply::json(&ResponseConfig {
             stakes: ConfigStakes {
@@ -59,6 +62,7 @@ pub async fn handler(_context: WrappedContext) -> Result<impl Reply, warp::Rejec
                     },
                 ],
             },
+            unstake_hints,
         }),
         StatusCode::OK,
     ))
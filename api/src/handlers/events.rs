@@ -0,0 +1,83 @@
+use crate::{context::WrappedContext, event_stream::StreamEvent, metrics};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use warp::{sse::Event, Reply};
+
+#[derive(Deserialize, Serialize, Debug, utoipa::IntoParams)]
+pub struct QueryParams {
+    query_vote_account: Option<String>,
+}
+
+fn to_sse_event(event: &StreamEvent) -> anyhow::Result<Event> {
+    let sse_event = Event::default().event(event.name()).id(event.id());
+    match event {
+        StreamEvent::Validator(validator_event) => Ok(sse_event.json_data(validator_event)?),
+        StreamEvent::ScoringRun(scoring_run) => Ok(sse_event.json_data(scoring_run)?),
+    }
+}
+
+async fn next_sse_event(
+    receiver: &mut Receiver<StreamEvent>,
+    query_vote_account: &Option<String>,
+) -> Option<Event> {
+    loop {
+        let event = match receiver.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Events stream is lagging, skipped {} events", skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => return None,
+        };
+
+        if let (Some(query_vote_account), Some(vote_account)) =
+            (query_vote_account, event.vote_account())
+        {
+            if query_vote_account != vote_account {
+                continue;
+            }
+        }
+
+        match to_sse_event(&event) {
+            Ok(sse_event) => return Some(sse_event),
+            Err(err) => error!("Failed to serialize event {}: {}", event.id(), err),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "Validators",
+    operation_id = "Stream validator events",
+    path = "/events",
+    params(QueryParams),
+    responses(
+        (status = 200, description = "Server-sent events `validator_event` (data: ValidatorEventRecord) and `scoring_run` (data: ScoringRun)", content_type = "text/event-stream")
+    )
+)]
+pub async fn handler(
+    query_params: QueryParams,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    info!("Streaming events {:?}", query_params.query_vote_account);
+    metrics::REQUEST_EVENTS.inc();
+
+    let receiver = context.read().await.events.subscribe();
+    let stream = futures::stream::unfold(
+        (receiver, query_params.query_vote_account),
+        |(mut receiver, query_vote_account)| async move {
+            next_sse_event(&mut receiver, &query_vote_account)
+                .await
+                .map(|sse_event| {
+                    (
+                        Ok::<_, Infallible>(sse_event),
+                        (receiver, query_vote_account),
+                    )
+                })
+        },
+    );
+
+    Ok(warp::sse::reply(warp::sse::keep_alive().stream(stream)))
+}
@@ -45,6 +45,7 @@ pub enum OrderField {
     Credits,
     MarinadeScore,
     Apy,
+    ApyWithMev,
     Commission,
     Uptime,
 }
@@ -157,6 +158,9 @@ pub async fn get_validators(
         OrderField::Apy => {
             |a: &&ValidatorRecord| Decimal::from(to_fixed_for_sort(a.avg_apy.unwrap_or(0.0)))
         }
+        OrderField::ApyWithMev => |a: &&ValidatorRecord| {
+            Decimal::from(to_fixed_for_sort(a.avg_apy_with_mev.unwrap_or(0.0)))
+        },
         OrderField::Commission => {
             |a: &&ValidatorRecord| Decimal::from(a.commission_max_observed.unwrap_or(100))
         }
@@ -0,0 +1,59 @@
+use crate::context::WrappedContext;
+use crate::metrics;
+use crate::utils::response_error;
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use store::dto::MevRecord;
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseMev {
+    mev: Vec<MevRecord>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct QueryParams {}
+
+#[utoipa::path(
+    get,
+    tag = "Validators",
+    operation_id = "List MEV history",
+    path = "/validators/<vote_account>/mev",
+    responses(
+        (status = 200, body = ResponseMev)
+    )
+)]
+pub async fn handler(
+    vote_account: String,
+    _query_params: QueryParams,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    info!("Fetching MEV {:?}", &vote_account);
+    metrics::REQUEST_COUNT_MEV.inc();
+
+    let validators = context.read().await.cache.get_validators();
+    let validator = validators.iter().find(|(_vote_key, record)| {
+        record.identity == vote_account || record.vote_account == vote_account
+    });
+
+    match validator {
+        Some((vote_key, _validator)) => {
+            let mev = context.read().await.cache.get_mev(vote_key);
+
+            Ok(match mev {
+                Some(mev) => warp::reply::with_status(json(&ResponseMev { mev }), StatusCode::OK),
+                _ => {
+                    error!("No MEV found for {}", &vote_account);
+                    response_error(StatusCode::NOT_FOUND, "Failed to fetch records!".into())
+                }
+            })
+        }
+        None => {
+            error!("No validator found for {}", &vote_account);
+            Ok(response_error(
+                StatusCode::NOT_FOUND,
+                "Failed to fetch records!".into(),
+            ))
+        }
+    }
+}
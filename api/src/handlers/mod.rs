@@ -1,15 +1,29 @@
+pub mod admin_blacklist;
+pub mod admin_blacklist_add;
+pub mod admin_blacklist_audit;
+pub mod admin_blacklist_proposal_review;
+pub mod admin_blacklist_proposals;
+pub mod admin_blacklist_remove;
 pub mod admin_score_upload;
 pub mod cluster_stats;
 pub mod commissions;
 pub mod config;
 pub mod docs;
+pub mod events;
 pub mod glossary;
 pub mod list_validators;
+pub mod mev;
 pub mod reports_commission_changes;
 pub mod reports_scoring;
+pub mod reports_scoring_diff;
 pub mod reports_scoring_html;
 pub mod reports_staking;
+pub mod scores_what_if;
+pub mod scoring_run_score_breakdown;
+pub mod scoring_run_scores;
+pub mod scoring_runs;
 pub mod unstake_hints;
+pub mod unstake_hints_history;
 pub mod uptimes;
 pub mod validator_score_breakdown;
 pub mod validator_scores;
@@ -0,0 +1,226 @@
+use crate::context::WrappedContext;
+use crate::handlers::scoring_runs::ScoringRun;
+use crate::utils::{response_error, response_error_500};
+use log::{error, info};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use store::dto::{ScoringRunRecord, ValidatorScoreDiff};
+use store::scoring_engine::diff_scores;
+use utoipa::IntoParams;
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseReportScoringDiff {
+    scoring_run_before: ScoringRun,
+    scoring_run_after: ScoringRun,
+    components: Vec<String>,
+    diff: Vec<ValidatorScoreDiff>,
+}
+
+#[derive(Deserialize, Serialize, Debug, IntoParams)]
+pub struct QueryParams {
+    /// Defaults to the scoring run preceding `scoring_run_id_after`
+    scoring_run_id_before: Option<i64>,
+    /// Defaults to the last scoring run
+    scoring_run_id_after: Option<i64>,
+    format: Option<ReportFormat>,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+fn option_to_string<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn diff_to_csv(components: &[String], diff: &[ValidatorScoreDiff]) -> anyhow::Result<String> {
+    let mut csv_content = csv::Writer::from_writer(Vec::new());
+
+    let mut header: Vec<String> = vec![
+        "vote_account".into(),
+        "rank_before".into(),
+        "rank_after".into(),
+        "rank_change".into(),
+        "score_before".into(),
+        "score_after".into(),
+        "score_delta".into(),
+    ];
+    header.extend(
+        components
+            .iter()
+            .map(|component| format!("delta_{}", component.to_lowercase())),
+    );
+    header.extend(
+        [
+            "eligible_stake_algo_before",
+            "eligible_stake_algo_after",
+            "eligible_stake_mnde_before",
+            "eligible_stake_mnde_after",
+            "eligible_stake_msol_before",
+            "eligible_stake_msol_after",
+            "target_stake_algo_before",
+            "target_stake_algo_after",
+            "target_stake_mnde_before",
+            "target_stake_mnde_after",
+            "target_stake_msol_before",
+            "target_stake_msol_after",
+            "target_stake_before",
+            "target_stake_after",
+        ]
+        .map(String::from),
+    );
+    csv_content.write_record(&header)?;
+
+    for d in diff {
+        let mut record: Vec<String> = vec![
+            d.vote_account.clone(),
+            option_to_string(d.rank_before),
+            option_to_string(d.rank_after),
+            option_to_string(d.rank_change),
+            option_to_string(d.score_before),
+            option_to_string(d.score_after),
+            option_to_string(d.score_delta),
+        ];
+        record.extend(
+            d.component_score_deltas
+                .iter()
+                .cloned()
+                .map(option_to_string),
+        );
+        record.extend([
+            option_to_string(d.eligible_stake_algo_before),
+            option_to_string(d.eligible_stake_algo_after),
+            option_to_string(d.eligible_stake_mnde_before),
+            option_to_string(d.eligible_stake_mnde_after),
+            option_to_string(d.eligible_stake_msol_before),
+            option_to_string(d.eligible_stake_msol_after),
+            d.target_stake_algo_before.to_string(),
+            d.target_stake_algo_after.to_string(),
+            d.target_stake_mnde_before.to_string(),
+            d.target_stake_mnde_after.to_string(),
+            d.target_stake_msol_before.to_string(),
+            d.target_stake_msol_after.to_string(),
+            d.target_stake_before.to_string(),
+            d.target_stake_after.to_string(),
+        ]);
+        csv_content.write_record(&record)?;
+    }
+
+    Ok(String::from_utf8(csv_content.into_inner()?)?)
+}
+
+fn select_scoring_runs(
+    scoring_runs: Vec<ScoringRunRecord>,
+    scoring_run_id_before: Option<i64>,
+    scoring_run_id_after: Option<i64>,
+) -> Option<(ScoringRunRecord, ScoringRunRecord)> {
+    let id = |scoring_run: &ScoringRunRecord| scoring_run.scoring_run_id.to_i64();
+
+    // Scoring runs are ordered from the latest one
+    let position_after = match scoring_run_id_after {
+        Some(scoring_run_id) => scoring_runs
+            .iter()
+            .position(|run| id(run) == Some(scoring_run_id))?,
+        None => 0,
+    };
+    let position_before = match scoring_run_id_before {
+        Some(scoring_run_id) => scoring_runs
+            .iter()
+            .position(|run| id(run) == Some(scoring_run_id))?,
+        None => position_after + 1,
+    };
+
+    Some((
+        scoring_runs.get(position_before)?.clone(),
+        scoring_runs.get(position_after)?.clone(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    tag = "Scoring",
+    operation_id = "Compare two scoring runs",
+    path = "reports/scoring-diff",
+    params(QueryParams),
+    responses(
+        (status = 200, body = ResponseReportScoringDiff)
+    )
+)]
+pub async fn handler(
+    query_params: QueryParams,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    info!("Serving the scoring diff report {:?}", query_params);
+
+    let psql_client = &context.read().await.psql_client;
+    let scoring_runs = match store::scoring::load_scoring_runs(psql_client).await {
+        Ok(scoring_runs) => scoring_runs,
+        Err(err) => {
+            error!("Failed to fetch scoring runs: {}", err);
+            return Ok(response_error_500("Failed to fetch scoring runs!".into()).into_response());
+        }
+    };
+
+    let (scoring_run_before, scoring_run_after) = match select_scoring_runs(
+        scoring_runs,
+        query_params.scoring_run_id_before,
+        query_params.scoring_run_id_after,
+    ) {
+        Some(scoring_runs) => scoring_runs,
+        None => {
+            return Ok(response_error(
+                StatusCode::NOT_FOUND,
+                "Scoring runs to compare not found!".into(),
+            )
+            .into_response())
+        }
+    };
+
+    let scores = futures::future::try_join(
+        store::utils::load_scores(psql_client, scoring_run_before.scoring_run_id),
+        store::utils::load_scores(psql_client, scoring_run_after.scoring_run_id),
+    )
+    .await;
+    let (scores_before, scores_after) = match scores {
+        Ok(scores) => scores,
+        Err(err) => {
+            error!("Failed to fetch scores: {}", err);
+            return Ok(response_error_500("Failed to fetch scores!".into()).into_response());
+        }
+    };
+
+    let components = scoring_run_after.components.clone();
+    let diff = diff_scores(
+        &scores_before,
+        &scoring_run_before.components,
+        &scores_after,
+        &components,
+    );
+
+    if query_params.format == Some(ReportFormat::Csv) {
+        return Ok(match diff_to_csv(&components, &diff) {
+            Ok(csv_content) => {
+                warp::reply::with_header(csv_content, "Content-Type", "text/csv").into_response()
+            }
+            Err(err) => {
+                error!("Failed to serialize the diff: {}", err);
+                response_error_500("Failed to serialize the diff!".into()).into_response()
+            }
+        });
+    }
+
+    Ok(warp::reply::with_status(
+        json(&ResponseReportScoringDiff {
+            scoring_run_before: scoring_run_before.into(),
+            scoring_run_after: scoring_run_after.into(),
+            components,
+            diff,
+        }),
+        StatusCode::OK,
+    )
+    .into_response())
+}
@@ -2,12 +2,15 @@ use crate::{cache::CachedScores, context::WrappedContext, metrics, utils::respon
 use log::{error, info};
 use serde::Serialize;
 use solana_program::native_token::LAMPORTS_PER_SOL;
-use store::utils::get_last_epoch;
+use std::collections::HashMap;
+use store::dto::DecentralizationImpact;
+use store::utils::{decentralization_impact, get_last_epoch, ValidatorStake};
 use warp::{http::StatusCode, reply, Reply};
 
 #[derive(Serialize, Debug, utoipa::ToSchema)]
 pub struct ResponseReportStaking {
     planned: Vec<Stake>,
+    decentralization_impact: DecentralizationImpact,
 }
 
 #[derive(Serialize, Debug, utoipa::ToSchema)]
@@ -28,9 +31,7 @@ pub struct StakingChange {
 }
 
 fn filter_and_sort_stakes(records: &mut Vec<StakingChange>) {
-    records.retain(|stake| {
-        stake.next_stake as f64 - stake.current_stake as f64 != 0.0
-    });
+    records.retain(|stake| stake.next_stake as f64 - stake.current_stake as f64 != 0.0);
     records.sort_by_key(|a| {
         if a.next_stake > 0 && a.next_stake > a.current_stake {
             -(a.next_stake as i64)
@@ -91,6 +92,70 @@ async fn get_planned_stakes(context: WrappedContext) -> anyhow::Result<Vec<Staki
     Ok(records)
 }
 
+/// Compares the cluster in the last epoch with the cluster after the planned stake changes
+async fn get_decentralization_impact(
+    context: WrappedContext,
+    planned_stakes: &[StakingChange],
+) -> anyhow::Result<DecentralizationImpact> {
+    let last_epoch = match get_last_epoch(&context.read().await.psql_client).await? {
+        Some(last_epoch) => last_epoch,
+        _ => return Ok(Default::default()),
+    };
+
+    let planned_stakes: HashMap<_, _> = planned_stakes
+        .iter()
+        .map(|planned_stake| (&planned_stake.vote_account, planned_stake))
+        .collect();
+    let validators = &context.read().await.cache.get_validators();
+
+    let mut stakes_before: Vec<ValidatorStake> = Default::default();
+    let mut stakes_after: Vec<ValidatorStake> = Default::default();
+    for validator in validators.values() {
+        let current_epoch_stats = match validator
+            .epoch_stats
+            .iter()
+            .find(|epoch_stats| epoch_stats.epoch == last_epoch)
+        {
+            Some(current_epoch_stats) => current_epoch_stats,
+            None => continue,
+        };
+        let stake = ValidatorStake {
+            vote_account: validator.vote_account.clone(),
+            dc_aso: validator.dc_aso.clone().unwrap_or("Unknown".to_string()),
+            dc_country: validator
+                .dc_country
+                .clone()
+                .unwrap_or("Unknown".to_string()),
+            dc_city: validator
+                .dc_full_city
+                .clone()
+                .unwrap_or("Unknown".to_string()),
+            activated_stake: current_epoch_stats.activated_stake,
+            marinade_stake: current_epoch_stats.marinade_stake,
+        };
+        let planned_stake = match planned_stakes.get(&validator.vote_account) {
+            Some(planned_stake) => ValidatorStake {
+                activated_stake: stake
+                    .activated_stake
+                    .saturating_sub(planned_stake.current_stake)
+                    + planned_stake.next_stake,
+                marinade_stake: planned_stake.next_stake,
+                ..stake.clone()
+            },
+            None => stake.clone(),
+        };
+
+        if stake.activated_stake > 0 {
+            stakes_before.push(stake);
+        }
+        if planned_stake.activated_stake > 0 {
+            stakes_after.push(planned_stake);
+        }
+    }
+
+    Ok(decentralization_impact(&stakes_before, &stakes_after))
+}
+
 #[utoipa::path(
     get,
     tag = "Scoring",
@@ -104,8 +169,19 @@ pub async fn handler(context: WrappedContext) -> Result<impl Reply, warp::Reject
     info!("Serving the staking report");
     metrics::REQUEST_COUNT_REPORT_STAKING.inc();
     let mut stakes: Vec<Stake> = Vec::new();
-    match get_planned_stakes(context).await {
+    match get_planned_stakes(context.clone()).await {
         Ok(mut planned_stakes) => {
+            let decentralization_impact =
+                match get_decentralization_impact(context, &planned_stakes).await {
+                    Ok(decentralization_impact) => decentralization_impact,
+                    Err(err) => {
+                        error!("Failed to compute the decentralization impact: {}", err);
+                        return Ok(response_error(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            "Failed to compute the decentralization impact".into(),
+                        ));
+                    }
+                };
             filter_and_sort_stakes(&mut planned_stakes);
             for planned_stake in planned_stakes {
                 if planned_stake.score > 0.0 || planned_stake.current_stake > 0 {
@@ -118,7 +194,10 @@ pub async fn handler(context: WrappedContext) -> Result<impl Reply, warp::Reject
                 }
             }
             return Ok(warp::reply::with_status(
-                reply::json(&ResponseReportStaking { planned: stakes }),
+                reply::json(&ResponseReportStaking {
+                    planned: stakes,
+                    decentralization_impact,
+                }),
                 StatusCode::OK,
             ));
         }
@@ -0,0 +1,151 @@
+use crate::cache::CachedScores;
+use crate::context::WrappedContext;
+use crate::metrics;
+use crate::utils::{response_error, response_error_500};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use store::dto::{ScoringConfig, ValidatorScoreDiff, ValidatorScoreRecord};
+use store::scoring_engine::{compute_scores, diff_scores, load_msol_votes, StakeControl};
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Deserialize, Debug, utoipa::ToSchema)]
+pub struct RequestScoresWhatIf {
+    /// Defaults to the config of the last scoring run
+    config: Option<ScoringConfig>,
+    /// Defaults to the mSOL votes from the CSV passed to the API
+    msol_votes: Option<HashMap<String, f64>>,
+}
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseScoresWhatIf {
+    config: ScoringConfig,
+    stake_control: StakeControl,
+    scores: Vec<ValidatorScoreRecord>,
+    compared_scoring_run_id: Option<i64>,
+    diff: Vec<ValidatorScoreDiff>,
+}
+
+#[utoipa::path(
+    post,
+    tag = "Scoring",
+    operation_id = "Simulate scoring with custom parameters",
+    path = "/validators/scores/what-if",
+    request_body = RequestScoresWhatIf,
+    responses(
+        (status = 200, body = ResponseScoresWhatIf)
+    )
+)]
+pub async fn handler(
+    request: RequestScoresWhatIf,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    metrics::REQUEST_COUNT_SCORES_WHAT_IF.inc();
+
+    log::info!("Simulating scoring {:?}", request.config);
+
+    let CachedScores {
+        scoring_run,
+        scores: current_scores,
+    } = context.read().await.cache.get_validators_scores();
+
+    let config = match request
+        .config
+        .or_else(|| scoring_run.as_ref().and_then(|run| run.config.clone()))
+    {
+        Some(config) => config,
+        None => {
+            return Ok(response_error(
+                StatusCode::BAD_REQUEST,
+                "No scoring config available!".into(),
+            ))
+        }
+    };
+    if let Err(err) = config.validate() {
+        return Ok(response_error(StatusCode::BAD_REQUEST, err.to_string()));
+    }
+
+    let blacklist =
+        match store::blacklist::load_active_blacklist(&context.read().await.psql_client).await {
+            Ok(blacklist) => blacklist,
+            Err(err) => {
+                log::error!("Failed to load the blacklist: {}", err);
+                return Ok(response_error_500("Failed to load the blacklist!".into()));
+            }
+        };
+
+    let msol_votes = match (request.msol_votes, &context.read().await.msol_votes_path) {
+        (Some(msol_votes), _) => msol_votes,
+        (None, Some(path)) => match load_msol_votes(path) {
+            Ok(msol_votes) => msol_votes,
+            Err(err) => {
+                log::error!("Failed to load the mSOL votes: {}", err);
+                return Ok(response_error_500("Failed to load the mSOL votes!".into()));
+            }
+        },
+        (None, None) => {
+            return Ok(response_error(
+                StatusCode::BAD_REQUEST,
+                "No mSOL votes available!".into(),
+            ))
+        }
+    };
+
+    let validators = context.read().await.cache.get_validators_flat();
+    let scoring_config = config.clone();
+    let result = match tokio::task::spawn_blocking(move || {
+        compute_scores(&validators, &blacklist, &msol_votes, &scoring_config)
+    })
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            log::error!("Failed to compute the scores: {}", err);
+            return Ok(response_error_500("Failed to simulate the scoring!".into()));
+        }
+    };
+
+    let components = config.components();
+    let scores: Result<HashMap<_, _>, _> = result
+        .scores
+        .iter()
+        .map(|row| {
+            store::utils::score_record_from_csv_row(&components, row, 0)
+                .map(|score| (score.vote_account.clone(), score))
+        })
+        .collect();
+    let scores = match scores {
+        Ok(scores) => scores,
+        Err(err) => {
+            log::error!("Failed to map the scores: {}", err);
+            return Ok(response_error_500("Failed to simulate the scoring!".into()));
+        }
+    };
+
+    let current_components = scoring_run
+        .as_ref()
+        .map(|run| run.components.clone())
+        .unwrap_or_default();
+    let diff = diff_scores(
+        &current_scores,
+        &current_components,
+        &scores,
+        &components
+            .iter()
+            .map(|component| component.to_string())
+            .collect::<Vec<_>>(),
+    );
+    let mut scores: Vec<_> = scores.into_values().collect();
+    scores.sort_by_key(|score| score.rank);
+
+    Ok(warp::reply::with_status(
+        json(&ResponseScoresWhatIf {
+            config,
+            stake_control: result.stake_control,
+            scores,
+            compared_scoring_run_id: scoring_run.and_then(|run| run.scoring_run_id.to_i64()),
+            diff,
+        }),
+        StatusCode::OK,
+    ))
+}
@@ -0,0 +1,70 @@
+use crate::context::WrappedContext;
+use crate::handlers::validator_score_breakdown::{
+    build_score_breakdown, QueryParams, ResponseScoreBreakdown,
+};
+use crate::metrics;
+use crate::utils::{response_error, response_error_500};
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[utoipa::path(
+    get,
+    tag = "Scoring",
+    operation_id = "Show score breakdown for a validator in a scoring run",
+    path = "/scoring-runs/{scoring_run_id}/score-breakdown",
+    params(
+        ("scoring_run_id" = i64, Path, description = "ID of the scoring run"),
+        QueryParams
+    ),
+    responses(
+        (status = 200, body = ResponseScoreBreakdown)
+    )
+)]
+pub async fn handler(
+    scoring_run_id: i64,
+    query_params: QueryParams,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    metrics::REQUEST_COUNT_SCORING_RUN_SCORE_BREAKDOWN.inc();
+
+    log::info!(
+        "Query validator score breakdown of the scoring run {} {:?}",
+        scoring_run_id,
+        query_params
+    );
+
+    let psql_client = &context.read().await.psql_client;
+    let scoring_run = match store::scoring::load_scoring_run(psql_client, scoring_run_id).await {
+        Ok(Some(scoring_run)) => scoring_run,
+        Ok(None) => {
+            return Ok(response_error(
+                StatusCode::NOT_FOUND,
+                "Scoring run not found!".into(),
+            ))
+        }
+        Err(err) => {
+            log::error!("Failed to load the scoring run: {}", err);
+            return Ok(response_error_500("Failed to load the scoring run!".into()));
+        }
+    };
+
+    let scores = match store::utils::load_scores(psql_client, scoring_run.scoring_run_id).await {
+        Ok(scores) => scores,
+        Err(err) => {
+            log::error!("Failed to load scores: {}", err);
+            return Ok(response_error_500("Failed to load scores!".into()));
+        }
+    };
+
+    Ok(
+        match build_score_breakdown(scoring_run, &scores, &query_params.query_vote_account) {
+            Some(score_breakdown) => warp::reply::with_status(
+                json(&ResponseScoreBreakdown { score_breakdown }),
+                StatusCode::OK,
+            ),
+            None => response_error(
+                StatusCode::NOT_FOUND,
+                "No score found for the validator!".into(),
+            ),
+        },
+    )
+}
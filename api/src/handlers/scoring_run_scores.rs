@@ -0,0 +1,67 @@
+use crate::context::WrappedContext;
+use crate::handlers::scoring_runs::ScoringRun;
+use crate::metrics;
+use crate::utils::{response_error, response_error_500};
+use serde::Serialize;
+use store::dto::ValidatorScoreRecord;
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseScoringRunScores {
+    scoring_run: ScoringRun,
+    scores: Vec<ValidatorScoreRecord>,
+}
+
+#[utoipa::path(
+    get,
+    tag = "Scoring",
+    operation_id = "List scores of a scoring run",
+    path = "/scoring-runs/{scoring_run_id}/scores",
+    params(
+        ("scoring_run_id" = i64, Path, description = "ID of the scoring run")
+    ),
+    responses(
+        (status = 200, body = ResponseScoringRunScores)
+    )
+)]
+pub async fn handler(
+    scoring_run_id: i64,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    metrics::REQUEST_COUNT_SCORING_RUN_SCORES.inc();
+
+    log::info!("Query scores of the scoring run {}", scoring_run_id);
+
+    let psql_client = &context.read().await.psql_client;
+    let scoring_run = match store::scoring::load_scoring_run(psql_client, scoring_run_id).await {
+        Ok(Some(scoring_run)) => scoring_run,
+        Ok(None) => {
+            return Ok(response_error(
+                StatusCode::NOT_FOUND,
+                "Scoring run not found!".into(),
+            ))
+        }
+        Err(err) => {
+            log::error!("Failed to load the scoring run: {}", err);
+            return Ok(response_error_500("Failed to load the scoring run!".into()));
+        }
+    };
+
+    let scores = match store::utils::load_scores(psql_client, scoring_run.scoring_run_id).await {
+        Ok(scores) => scores,
+        Err(err) => {
+            log::error!("Failed to load scores: {}", err);
+            return Ok(response_error_500("Failed to load scores!".into()));
+        }
+    };
+    let mut scores: Vec<_> = scores.into_values().collect();
+    scores.sort_by_key(|score| score.rank);
+
+    Ok(warp::reply::with_status(
+        json(&ResponseScoringRunScores {
+            scoring_run: scoring_run.into(),
+            scores,
+        }),
+        StatusCode::OK,
+    ))
+}
@@ -0,0 +1,69 @@
+use crate::context::WrappedContext;
+use crate::metrics;
+use crate::utils::response_error_500;
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use serde::Serialize;
+use store::dto::{ScoringConfig, ScoringRunRecord};
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Serialize, Debug, utoipa::ToSchema)]
+pub struct ResponseScoringRuns {
+    scoring_runs: Vec<ScoringRun>,
+}
+
+#[derive(Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct ScoringRun {
+    pub scoring_run_id: i64,
+    pub created_at: DateTime<Utc>,
+    pub epoch: i32,
+    pub ui_id: String,
+    pub components: Vec<String>,
+    pub component_weights: Vec<f64>,
+    pub config: Option<ScoringConfig>,
+}
+
+impl From<ScoringRunRecord> for ScoringRun {
+    fn from(scoring_run: ScoringRunRecord) -> Self {
+        Self {
+            scoring_run_id: scoring_run.scoring_run_id.to_i64().unwrap_or_default(),
+            created_at: scoring_run.created_at,
+            epoch: scoring_run.epoch,
+            ui_id: scoring_run.ui_id,
+            components: scoring_run.components,
+            component_weights: scoring_run.component_weights,
+            config: scoring_run.config,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "Scoring",
+    operation_id = "List scoring runs",
+    path = "/scoring-runs",
+    responses(
+        (status = 200, body = ResponseScoringRuns)
+    )
+)]
+pub async fn handler(context: WrappedContext) -> Result<impl Reply, warp::Rejection> {
+    metrics::REQUEST_COUNT_SCORING_RUNS.inc();
+
+    log::info!("Query scoring runs");
+
+    let scoring_runs =
+        match store::scoring::load_scoring_runs(&context.read().await.psql_client).await {
+            Ok(scoring_runs) => scoring_runs,
+            Err(err) => {
+                log::error!("Failed to load scoring runs: {}", err);
+                return Ok(response_error_500("Failed to load scoring runs!".into()));
+            }
+        };
+
+    Ok(warp::reply::with_status(
+        json(&ResponseScoringRuns {
+            scoring_runs: scoring_runs.into_iter().map(ScoringRun::from).collect(),
+        }),
+        StatusCode::OK,
+    ))
+}
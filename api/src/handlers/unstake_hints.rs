@@ -32,8 +32,8 @@ pub async fn handler(
 
     let unstake_hints = store::scoring::load_unstake_hints(
         &context.read().await.psql_client,
-        &context.read().await.blacklist_path,
         query_params.epoch,
+        &context.read().await.unstake_hints_config,
     )
     .await;
 
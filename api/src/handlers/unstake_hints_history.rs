@@ -0,0 +1,54 @@
+use crate::{context::WrappedContext, metrics, utils::response_error_500};
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use warp::{http::StatusCode, reply::json, Reply};
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ResponseUnstakeHintsHistory {
+    unstake_hints_history: Vec<store::dto::UnstakeHintHistoryRecord>,
+}
+
+#[derive(Deserialize, Serialize, Debug, utoipa::IntoParams)]
+pub struct QueryParams {
+    query_vote_account: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    tag = "Scoring",
+    operation_id = "List unstake hints history",
+    path = "/unstake-hints/history",
+    params(QueryParams),
+    responses(
+        (status = 200, body = ResponseUnstakeHintsHistory)
+    )
+)]
+pub async fn handler(
+    query_params: QueryParams,
+    context: WrappedContext,
+) -> Result<impl Reply, warp::Rejection> {
+    info!(
+        "Fetching unstake hints history {:?}",
+        query_params.query_vote_account
+    );
+    metrics::REQUEST_UNSTAKE_HINTS_HISTORY.inc();
+
+    let unstake_hints_history = store::scoring::load_unstake_hints_history(
+        &context.read().await.psql_client,
+        query_params.query_vote_account,
+    )
+    .await;
+
+    Ok(match unstake_hints_history {
+        Ok(unstake_hints_history) => warp::reply::with_status(
+            json(&ResponseUnstakeHintsHistory {
+                unstake_hints_history,
+            }),
+            StatusCode::OK,
+        ),
+        Err(err) => {
+            error!("Failed to load unstake hints history: {}", err);
+            response_error_500("Failed to load unstake hints history!".into())
+        }
+    })
+}
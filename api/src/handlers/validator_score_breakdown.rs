@@ -3,19 +3,20 @@ use crate::metrics;
 use crate::{context::WrappedContext, utils::response_error};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use store::dto::{ScoringRunRecord, ValidatorScoreRecord};
+use std::collections::HashMap;
+use store::dto::{ScoringConfig, ScoringRunRecord, ValidatorScoreRecord};
 use store::utils::to_fixed_for_sort;
 use utoipa::IntoParams;
 use warp::{http::StatusCode, reply::json, Reply};
 
 #[derive(Serialize, Debug, utoipa::ToSchema)]
 pub struct ResponseScoreBreakdown {
-    score_breakdown: ScoreBreakdown,
+    pub score_breakdown: ScoreBreakdown,
 }
 
 #[derive(Deserialize, Serialize, Debug, IntoParams)]
 pub struct QueryParams {
-    query_vote_account: String,
+    pub query_vote_account: String,
 }
 
 #[derive(Deserialize, Serialize, Debug, utoipa::ToSchema)]
@@ -41,6 +42,7 @@ pub struct ScoreBreakdown {
     pub created_at: DateTime<Utc>,
     pub epoch: i32,
     pub ui_id: String,
+    pub config: Option<ScoringConfig>,
 }
 
 #[utoipa::path(
@@ -66,14 +68,7 @@ pub async fn handler(
         scoring_run,
     } = context.read().await.cache.get_validators_scores();
 
-    let ScoringRunRecord {
-        created_at,
-        epoch,
-        components,
-        component_weights,
-        ui_id,
-        ..
-    } = match scoring_run {
+    let scoring_run = match scoring_run {
         Some(scoring_run) => scoring_run,
         None => {
             log::warn!("No scoring run is present in the cache!");
@@ -84,6 +79,39 @@ pub async fn handler(
         }
     };
 
+    let score_breakdown =
+        match build_score_breakdown(scoring_run, &scores, &query_params.query_vote_account) {
+            Some(score_breakdown) => score_breakdown,
+            None => {
+                log::warn!("No score found for the validator!");
+                return Ok(response_error(
+                    StatusCode::OK,
+                    "No score found for the validator!".into(),
+                ));
+            }
+        };
+
+    Ok(warp::reply::with_status(
+        json(&ResponseScoreBreakdown { score_breakdown }),
+        StatusCode::OK,
+    ))
+}
+
+pub fn build_score_breakdown(
+    scoring_run: ScoringRunRecord,
+    scores: &HashMap<String, ValidatorScoreRecord>,
+    vote_account: &String,
+) -> Option<ScoreBreakdown> {
+    let ScoringRunRecord {
+        created_at,
+        epoch,
+        components,
+        component_weights,
+        ui_id,
+        config,
+        ..
+    } = scoring_run;
+
     let ValidatorScoreRecord {
         vote_account,
         score,
@@ -100,16 +128,7 @@ pub async fn handler(
         target_stake_mnde,
         target_stake_msol,
         scoring_run_id,
-    } = match scores.get(&query_params.query_vote_account).cloned() {
-        Some(score) => score,
-        None => {
-            log::warn!("No score found for the validator!");
-            return Ok(response_error(
-                StatusCode::OK,
-                "No score found for the validator!".into(),
-            ));
-        }
-    };
+    } = scores.get(vote_account).cloned()?;
 
     let min_score_eligible_algo = scores
         .iter()
@@ -117,32 +136,28 @@ pub async fn handler(
         .map(|(_, ValidatorScoreRecord { score, .. })| *score)
         .min_by(|a, b| to_fixed_for_sort(*a).cmp(&to_fixed_for_sort(*b)));
 
-    Ok(warp::reply::with_status(
-        json(&ResponseScoreBreakdown {
-            score_breakdown: ScoreBreakdown {
-                vote_account,
-                score,
-                min_score_eligible_algo,
-                rank,
-                ui_hints,
-                mnde_votes,
-                component_scores,
-                component_ranks,
-                component_values,
-                component_weights,
-                components,
-                eligible_stake_algo,
-                eligible_stake_mnde,
-                eligible_stake_msol,
-                target_stake_algo,
-                target_stake_mnde,
-                target_stake_msol,
-                scoring_run_id,
-                created_at,
-                epoch,
-                ui_id,
-            },
-        }),
-        StatusCode::OK,
-    ))
+    Some(ScoreBreakdown {
+        vote_account,
+        score,
+        min_score_eligible_algo,
+        rank,
+        ui_hints,
+        mnde_votes,
+        component_scores,
+        component_ranks,
+        component_values,
+        component_weights,
+        components,
+        eligible_stake_algo,
+        eligible_stake_mnde,
+        eligible_stake_msol,
+        target_stake_algo,
+        target_stake_mnde,
+        target_stake_msol,
+        scoring_run_id,
+        created_at,
+        epoch,
+        ui_id,
+        config,
+    })
 }
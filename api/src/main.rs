@@ -1,9 +1,12 @@
 use crate::context::{Context, WrappedContext};
 use crate::handlers::{
-    admin_score_upload, cluster_stats, commissions, config, docs, glossary, list_validators,
-    reports_commission_changes, reports_scoring, reports_scoring_html, reports_staking,
-    unstake_hints, uptimes, validator_score_breakdown, validator_scores, validators_flat, versions,
-    workflow_metrics_upload,
+    admin_blacklist, admin_blacklist_add, admin_blacklist_audit, admin_blacklist_proposal_review,
+    admin_blacklist_proposals, admin_blacklist_remove, admin_score_upload, cluster_stats,
+    commissions, config, docs, events, glossary, list_validators, mev, reports_commission_changes,
+    reports_scoring, reports_scoring_diff, reports_scoring_html, reports_staking, scores_what_if,
+    scoring_run_score_breakdown, scoring_run_scores, scoring_runs, unstake_hints,
+    unstake_hints_history, uptimes, validator_score_breakdown, validator_scores, validators_flat,
+    versions, workflow_metrics_upload,
 };
 use env_logger::Env;
 use log::{error, info};
@@ -17,6 +20,7 @@ use warp::{Filter, Rejection};
 pub mod api_docs;
 pub mod cache;
 pub mod context;
+pub mod event_stream;
 pub mod handlers;
 pub mod metrics;
 pub mod utils;
@@ -29,8 +33,14 @@ pub struct Params {
     #[structopt(long = "glossary-path")]
     glossary_path: String,
 
-    #[structopt(long = "blacklist-path")]
-    blacklist_path: String,
+    #[structopt(long = "unstake-hints-config-path")]
+    unstake_hints_config_path: Option<String>,
+
+    #[structopt(
+        long = "msol-votes-path",
+        help = "CSV with the mSOL votes used by the scoring what-if"
+    )]
+    msol_votes_path: Option<String>,
 
     #[structopt(env = "ADMIN_AUTH_TOKEN", long = "admin-auth-token")]
     admin_auth_token: String,
@@ -50,12 +60,20 @@ async fn main() -> anyhow::Result<()> {
         }
     });
 
+    let unstake_hints_config = match &params.unstake_hints_config_path {
+        Some(path) => store::scoring::load_unstake_hints_config(path)?,
+        None => Default::default(),
+    };
+    info!("Unstake hints config: {:?}", unstake_hints_config);
+
     let context = Arc::new(RwLock::new(Context::new(
         psql_client,
         params.glossary_path,
-        params.blacklist_path,
+        params.msol_votes_path,
+        unstake_hints_config,
     )?));
     cache::spawn_cache_warmer(context.clone());
+    event_stream::spawn_events_poller(context.clone(), context.read().await.events.clone());
 
     let cors = warp::cors()
         .allow_any_origin()
@@ -68,7 +86,7 @@ async fn main() -> anyhow::Result<()> {
             "Access-Control-Request-Method",
             "Access-Control-Request-Headers",
         ])
-        .allow_methods(vec!["POST", "GET"]);
+        .allow_methods(vec!["POST", "GET", "DELETE"]);
 
     let top_level = warp::path::end()
         .and(warp::get())
@@ -78,9 +96,7 @@ async fn main() -> anyhow::Result<()> {
         .and(warp::get())
         .map(|| warp::reply::json(&<crate::api_docs::ApiDoc as utoipa::OpenApi>::openapi()));
 
-    let route_api_docs_html = warp::path("docs")
-        .and(warp::get())
-        .and_then(docs::handler);
+    let route_api_docs_html = warp::path("docs").and(warp::get()).and_then(docs::handler);
 
     let route_validators = warp::path!("validators")
         .and(warp::path::end())
@@ -102,6 +118,33 @@ async fn main() -> anyhow::Result<()> {
         .and(with_context(context.clone()))
         .and_then(validator_scores::handler);
 
+    let route_scores_what_if = warp::path!("validators" / "scores" / "what-if")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::content_length_limit(5_000_000))
+        .and(warp::body::json::<scores_what_if::RequestScoresWhatIf>())
+        .and(with_context(context.clone()))
+        .and_then(scores_what_if::handler);
+
+    let route_scoring_runs = warp::path!("scoring-runs")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_context(context.clone()))
+        .and_then(scoring_runs::handler);
+
+    let route_scoring_run_scores = warp::path!("scoring-runs" / i64 / "scores")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_context(context.clone()))
+        .and_then(scoring_run_scores::handler);
+
+    let route_scoring_run_score_breakdown = warp::path!("scoring-runs" / i64 / "score-breakdown")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<validator_score_breakdown::QueryParams>())
+        .and(with_context(context.clone()))
+        .and_then(scoring_run_score_breakdown::handler);
+
     let route_validators_flat = warp::path!("validators" / "flat")
         .and(warp::path::end())
         .and(warp::get())
@@ -137,6 +180,13 @@ async fn main() -> anyhow::Result<()> {
         .and(with_context(context.clone()))
         .and_then(commissions::handler);
 
+    let route_mev = warp::path!("validators" / String / "mev")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<mev::QueryParams>())
+        .and(with_context(context.clone()))
+        .and_then(mev::handler);
+
     let route_glossary = warp::path!("static" / "glossary.md")
         .and(warp::path::end())
         .and(warp::get())
@@ -167,12 +217,26 @@ async fn main() -> anyhow::Result<()> {
         .and(with_context(context.clone()))
         .and_then(reports_scoring_html::handler);
 
+    let route_reports_scoring_diff = warp::path!("reports" / "scoring-diff")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<reports_scoring_diff::QueryParams>())
+        .and(with_context(context.clone()))
+        .and_then(reports_scoring_diff::handler);
+
     let route_reports_staking = warp::path!("reports" / "staking")
         .and(warp::path::end())
         .and(warp::get())
         .and(with_context(context.clone()))
         .and_then(reports_staking::handler);
 
+    let route_events = warp::path!("events")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<events::QueryParams>())
+        .and(with_context(context.clone()))
+        .and_then(events::handler);
+
     let route_unstake_hints = warp::path!("unstake-hints")
         .and(warp::path::end())
         .and(warp::get())
@@ -180,6 +244,13 @@ async fn main() -> anyhow::Result<()> {
         .and(with_context(context.clone()))
         .and_then(unstake_hints::handler);
 
+    let route_unstake_hints_history = warp::path!("unstake-hints" / "history")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<unstake_hints_history::QueryParams>())
+        .and(with_context(context.clone()))
+        .and_then(unstake_hints_history::handler);
+
     let route_admin_upload_score = warp::path!("admin" / "scores")
         .and(warp::path::end())
         .and(warp::post())
@@ -189,6 +260,58 @@ async fn main() -> anyhow::Result<()> {
         .and(with_context(context.clone()))
         .and_then(admin_score_upload::handler);
 
+    let route_admin_blacklist = warp::path!("admin" / "blacklist")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_admin_auth(params.admin_auth_token.clone()))
+        .and(warp::query::<admin_blacklist::QueryParams>())
+        .and(with_context(context.clone()))
+        .and_then(admin_blacklist::handler);
+
+    let route_admin_blacklist_add = warp::path!("admin" / "blacklist")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(with_admin_auth(params.admin_auth_token.clone()))
+        .and(warp::body::content_length_limit(100_000))
+        .and(warp::body::json::<
+            admin_blacklist_add::RequestAdminBlacklistAdd,
+        >())
+        .and(with_context(context.clone()))
+        .and_then(admin_blacklist_add::handler);
+
+    let route_admin_blacklist_audit = warp::path!("admin" / "blacklist" / "audit")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_admin_auth(params.admin_auth_token.clone()))
+        .and(warp::query::<admin_blacklist_audit::QueryParams>())
+        .and(with_context(context.clone()))
+        .and_then(admin_blacklist_audit::handler);
+
+    let route_admin_blacklist_proposals = warp::path!("admin" / "blacklist" / "proposals")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_admin_auth(params.admin_auth_token.clone()))
+        .and(warp::query::<admin_blacklist_proposals::QueryParams>())
+        .and(with_context(context.clone()))
+        .and_then(admin_blacklist_proposals::handler);
+
+    let route_admin_blacklist_proposal_review =
+        warp::path!("admin" / "blacklist" / "proposals" / i64)
+            .and(warp::path::end())
+            .and(warp::post())
+            .and(with_admin_auth(params.admin_auth_token.clone()))
+            .and(warp::query::<admin_blacklist_proposal_review::QueryParams>())
+            .and(with_context(context.clone()))
+            .and_then(admin_blacklist_proposal_review::handler);
+
+    let route_admin_blacklist_remove = warp::path!("admin" / "blacklist" / String)
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(with_admin_auth(params.admin_auth_token.clone()))
+        .and(warp::query::<admin_blacklist_remove::QueryParams>())
+        .and(with_context(context.clone()))
+        .and_then(admin_blacklist_remove::handler);
+
     let route_workflow_metrics_upload = warp::path!("admin" / "metrics")
         .and(warp::path::end())
         .and(warp::post())
@@ -203,18 +326,32 @@ async fn main() -> anyhow::Result<()> {
         .or(route_validators)
         .or(route_validator_score_breakdown)
         .or(route_validator_scores)
+        .or(route_scores_what_if)
+        .or(route_scoring_runs)
+        .or(route_scoring_run_scores)
+        .or(route_scoring_run_score_breakdown)
         .or(route_validators_flat)
         .or(route_uptimes)
         .or(route_versions)
         .or(route_commissions)
+        .or(route_mev)
         .or(route_glossary)
         .or(route_config)
         .or(route_reports_scoring)
         .or(route_reports_scoring_html)
+        .or(route_reports_scoring_diff)
         .or(route_reports_staking)
+        .or(route_events)
         .or(route_unstake_hints)
+        .or(route_unstake_hints_history)
         .or(route_reports_commission_changes)
         .or(route_admin_upload_score)
+        .or(route_admin_blacklist)
+        .or(route_admin_blacklist_add)
+        .or(route_admin_blacklist_audit)
+        .or(route_admin_blacklist_proposals)
+        .or(route_admin_blacklist_proposal_review)
+        .or(route_admin_blacklist_remove)
         .or(route_workflow_metrics_upload)
         .with(cors);
 
@@ -11,11 +11,21 @@ lazy_static! {
         "How many times /cluster-stats endpoint was requested"
     )
     .unwrap();
+    pub static ref REQUEST_EVENTS: IntCounter = register_int_counter!(
+        "ds_request_count_events",
+        "How many times /events endpoint was requested"
+    )
+    .unwrap();
     pub static ref REQUEST_UNSTAKE_HINTS: IntCounter = register_int_counter!(
         "ds_request_count_unstake_hints",
         "How many times /unstake-hints endpoint was requested"
     )
     .unwrap();
+    pub static ref REQUEST_UNSTAKE_HINTS_HISTORY: IntCounter = register_int_counter!(
+        "ds_request_count_unstake_hints_history",
+        "How many times /unstake-hints/history endpoint was requested"
+    )
+    .unwrap();
     pub static ref REQUEST_COUNT_VALIDATORS: IntCounter = register_int_counter!(
         "ds_request_count_validators",
         "How many times /validators endpoint was requested"
@@ -56,11 +66,66 @@ lazy_static! {
         "How many times /uptimes endpoint was requested"
     )
     .unwrap();
+    pub static ref REQUEST_COUNT_MEV: IntCounter = register_int_counter!(
+        "ds_request_count_mev",
+        "How many times /mev endpoint was requested"
+    )
+    .unwrap();
+    pub static ref REQUEST_COUNT_SCORES_WHAT_IF: IntCounter = register_int_counter!(
+        "ds_request_count_scores_what_if",
+        "How many times /validators/scores/what-if endpoint was requested"
+    )
+    .unwrap();
+    pub static ref REQUEST_COUNT_SCORING_RUNS: IntCounter = register_int_counter!(
+        "ds_request_count_scoring_runs",
+        "How many times /scoring-runs endpoint was requested"
+    )
+    .unwrap();
+    pub static ref REQUEST_COUNT_SCORING_RUN_SCORES: IntCounter = register_int_counter!(
+        "ds_request_count_scoring_run_scores",
+        "How many times /scoring-runs/{id}/scores endpoint was requested"
+    )
+    .unwrap();
+    pub static ref REQUEST_COUNT_SCORING_RUN_SCORE_BREAKDOWN: IntCounter = register_int_counter!(
+        "ds_request_count_scoring_run_score_breakdown",
+        "How many times /scoring-runs/{id}/score-breakdown endpoint was requested"
+    )
+    .unwrap();
     pub static ref REQUEST_ADMIN_SCORE_UPLOAD: IntCounter = register_int_counter!(
         "ds_request_count_admin_score_upload",
         "How many times /admin/scores endpoint was requested"
     )
     .unwrap();
+    pub static ref REQUEST_ADMIN_BLACKLIST: IntCounter = register_int_counter!(
+        "ds_request_count_admin_blacklist",
+        "How many times /admin/blacklist endpoint was requested"
+    )
+    .unwrap();
+    pub static ref REQUEST_ADMIN_BLACKLIST_ADD: IntCounter = register_int_counter!(
+        "ds_request_count_admin_blacklist_add",
+        "How many times POST /admin/blacklist endpoint was requested"
+    )
+    .unwrap();
+    pub static ref REQUEST_ADMIN_BLACKLIST_REMOVE: IntCounter = register_int_counter!(
+        "ds_request_count_admin_blacklist_remove",
+        "How many times DELETE /admin/blacklist/<vote_account> endpoint was requested"
+    )
+    .unwrap();
+    pub static ref REQUEST_ADMIN_BLACKLIST_AUDIT: IntCounter = register_int_counter!(
+        "ds_request_count_admin_blacklist_audit",
+        "How many times /admin/blacklist/audit endpoint was requested"
+    )
+    .unwrap();
+    pub static ref REQUEST_ADMIN_BLACKLIST_PROPOSALS: IntCounter = register_int_counter!(
+        "ds_request_count_admin_blacklist_proposals",
+        "How many times /admin/blacklist/proposals endpoint was requested"
+    )
+    .unwrap();
+    pub static ref REQUEST_ADMIN_BLACKLIST_PROPOSAL_REVIEW: IntCounter = register_int_counter!(
+        "ds_request_count_admin_blacklist_proposal_review",
+        "How many times /admin/blacklist/proposals/<blacklist_proposal_id> endpoint was requested"
+    )
+    .unwrap();
     pub static ref JOB_COUNT_SCHEDULED: IntCounter =
         register_int_counter!("ds_job_count_scheduled", "How many jobs were scheduled").unwrap();
     pub static ref JOB_COUNT_SUCCESS: IntCounter =
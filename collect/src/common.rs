@@ -2,9 +2,60 @@ use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 pub struct CommonParams {
-    #[structopt(short = "u", long = "url")]
-    pub rpc_url: String,
+    #[structopt(
+        short = "u",
+        long = "url",
+        env = "RPC_URL",
+        required_unless = "rpc-replay-path",
+        number_of_values = 1,
+        use_delimiter = true,
+        help = "RPC endpoints, the following ones are used when a call fails on the previous one"
+    )]
+    pub rpc_urls: Vec<String>,
 
     #[structopt(short = "c", long = "commitment", default_value = "finalized")]
     pub commitment: String,
+
+    #[structopt(
+        long = "rpc-max-retries",
+        default_value = "5",
+        help = "Retries of a failed RPC call before giving up"
+    )]
+    pub rpc_max_retries: u32,
+
+    #[structopt(
+        long = "rpc-backoff-ms",
+        default_value = "500",
+        help = "Delay before the first retry of a failed RPC call, doubled with each retry"
+    )]
+    pub rpc_backoff_ms: u64,
+
+    #[structopt(long = "rpc-max-backoff-ms", default_value = "30000")]
+    pub rpc_max_backoff_ms: u64,
+
+    #[structopt(
+        long = "rpc-rate-limit",
+        default_value = "0",
+        help = "Max RPC requests per second to a single endpoint, 0 for no limit"
+    )]
+    pub rpc_rate_limit: u32,
+
+    #[structopt(
+        long = "rpc-report-file",
+        help = "File to write the report of failed RPC calls to"
+    )]
+    pub rpc_report_path: Option<String>,
+
+    #[structopt(
+        long = "rpc-record-file",
+        help = "File to record the responses of all RPC calls to"
+    )]
+    pub rpc_record_path: Option<String>,
+
+    #[structopt(
+        long = "rpc-replay-file",
+        conflicts_with = "rpc-record-path",
+        help = "File with recorded RPC calls to answer the RPC calls from instead of the endpoints"
+    )]
+    pub rpc_replay_path: Option<String>,
 }
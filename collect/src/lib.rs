@@ -1,5 +1,7 @@
 pub mod common;
 pub mod marinade_service;
+pub mod rpc_fixture;
+pub mod rpc_service;
 pub mod solana_service;
 pub mod validators;
 pub mod validators_mev;
@@ -18,21 +18,24 @@ struct Params {
 
 #[derive(Debug, StructOpt)]
 enum CollectCommand {
-    Validators(ValidatorsOptions),
+    Validators(Box<ValidatorsOptions>),
     ValidatorsPerformance(ValidatorsPerformanceOptions),
     ValidatorsMEV,
 }
 
-fn main() -> anyhow::Result<()> {
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
     env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
 
     let params = Params::from_args();
 
     Ok(match params.command {
-        CollectCommand::Validators(options) => collect_validators_info(params.common, options),
+        CollectCommand::Validators(options) => {
+            collect_validators_info(params.common, *options).await
+        }
         CollectCommand::ValidatorsPerformance(options) => {
-            collect_validators_performance_info(params.common, options)
+            collect_validators_performance_info(params.common, options).await
         }
-        CollectCommand::ValidatorsMEV => collect_validators_mev_info(params.common),
+        CollectCommand::ValidatorsMEV => collect_validators_mev_info(params.common).await,
     }?)
 }
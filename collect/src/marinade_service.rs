@@ -2,41 +2,126 @@ use anchor_lang::prelude::*;
 use log::info;
 use solana_account_decoder::*;
 use solana_client::{
-    rpc_client::RpcClient,
+    nonblocking::rpc_client::RpcClient,
     rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
     rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
 };
 use solana_program::{clock::*, pubkey::Pubkey};
 use solana_sdk::stake;
 use std::collections::*;
+use std::str::FromStr;
+
+const MARINADE_STATE: &str = "8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC";
+const DECENTRALIZER_AUTHORITY: &str = "noMa7dN4cHQLV4ZonXrC29HTKFpxrpFbDLK5Gub8W8t";
+const STAKE_DEPOSIT_SEED: &[u8] = b"deposit";
+const STAKE_WITHDRAW_SEED: &[u8] = b"withdraw";
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct MarinadeConfig {
+    /// State account of the Marinade liquid staking program, the stake authorities are derived from it
+    pub state: String,
+    /// Set both to use these authorities instead of the ones derived from the state
+    pub stake_delegation_authority: Option<String>,
+    pub stake_withdrawer_authority: Option<String>,
+    pub decentralizer_authority: String,
+}
 
-pub fn get_marinade_stakes(rpc_client: &RpcClient) -> anyhow::Result<HashMap<String, u64>> {
-    // @todo take from state
-    let delegation_authority = "4bZ6o3eUUNXhKuqjdCnCoPAoLgWiuLYixKaxoa8PpiKk".try_into()?;
-    let withdrawer_authority = "9eG63CdHjsfhHmobHgLtESGC8GabbmRcaSpHAZrtmhco".try_into()?;
-    Ok(get_stakes_groupped_by_validator(
-        rpc_client,
-        &delegation_authority,
-        Some(&withdrawer_authority),
-    )?)
+impl Default for MarinadeConfig {
+    fn default() -> Self {
+        Self {
+            state: MARINADE_STATE.to_string(),
+            stake_delegation_authority: None,
+            stake_withdrawer_authority: None,
+            decentralizer_authority: DECENTRALIZER_AUTHORITY.to_string(),
+        }
+    }
+}
+
+pub fn load_marinade_config(config_path: &Option<String>) -> anyhow::Result<MarinadeConfig> {
+    match config_path {
+        Some(config_path) => Ok(serde_yaml::from_reader(std::fs::File::open(config_path)?)?),
+        None => Ok(Default::default()),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MarinadeAuthorities {
+    pub stake_delegation_authority: Pubkey,
+    pub stake_withdrawer_authority: Pubkey,
+    pub decentralizer_authority: Pubkey,
+}
+
+pub async fn get_marinade_authorities(
+    rpc_client: &RpcClient,
+    config: &MarinadeConfig,
+) -> anyhow::Result<MarinadeAuthorities> {
+    let decentralizer_authority = Pubkey::from_str(&config.decentralizer_authority)?;
+
+    if let (Some(stake_delegation_authority), Some(stake_withdrawer_authority)) = (
+        &config.stake_delegation_authority,
+        &config.stake_withdrawer_authority,
+    ) {
+        return Ok(MarinadeAuthorities {
+            stake_delegation_authority: Pubkey::from_str(stake_delegation_authority)?,
+            stake_withdrawer_authority: Pubkey::from_str(stake_withdrawer_authority)?,
+            decentralizer_authority,
+        });
+    }
+
+    info!(
+        "Deriving stake authorities from the Marinade state: {}",
+        config.state
+    );
+    let state = Pubkey::from_str(&config.state)?;
+    let state_account = rpc_client.get_account(&state).await?;
+    let discriminator = solana_program::hash::hash(b"account:State").to_bytes();
+    if state_account.data.get(..8) != Some(&discriminator[..8]) {
+        anyhow::bail!("Account {} is not a Marinade state", state);
+    }
+
+    let program_id = state_account.owner;
+    let (stake_delegation_authority, _) =
+        Pubkey::find_program_address(&[state.as_ref(), STAKE_DEPOSIT_SEED], &program_id);
+    let (stake_withdrawer_authority, _) =
+        Pubkey::find_program_address(&[state.as_ref(), STAKE_WITHDRAW_SEED], &program_id);
+
+    Ok(MarinadeAuthorities {
+        stake_delegation_authority,
+        stake_withdrawer_authority,
+        decentralizer_authority,
+    })
 }
 
-pub fn get_decentralizer_stakes(rpc_client: &RpcClient) -> anyhow::Result<HashMap<String, u64>> {
-    // @todo take from config
-    let decentralizer_authority = "noMa7dN4cHQLV4ZonXrC29HTKFpxrpFbDLK5Gub8W8t".try_into()?;
+pub async fn get_marinade_stakes(
+    rpc_client: &RpcClient,
+    authorities: &MarinadeAuthorities,
+) -> anyhow::Result<HashMap<String, u64>> {
     Ok(get_stakes_groupped_by_validator(
         rpc_client,
-        &decentralizer_authority,
-        None,
-    )?)
+        &authorities.stake_delegation_authority,
+        Some(&authorities.stake_withdrawer_authority),
+    )
+    .await?)
+}
+
+pub async fn get_decentralizer_stakes(
+    rpc_client: &RpcClient,
+    authorities: &MarinadeAuthorities,
+) -> anyhow::Result<HashMap<String, u64>> {
+    Ok(
+        get_stakes_groupped_by_validator(rpc_client, &authorities.decentralizer_authority, None)
+            .await?,
+    )
 }
 
-fn get_stakes_groupped_by_validator(
+async fn get_stakes_groupped_by_validator(
     rpc_client: &RpcClient,
     delegation_authority: &Pubkey,
     withdrawer_authority: Option<&Pubkey>,
 ) -> anyhow::Result<HashMap<String, u64>> {
-    let stakes = get_stake_accounts(rpc_client, &delegation_authority, withdrawer_authority)?;
+    let stakes =
+        get_stake_accounts(rpc_client, &delegation_authority, withdrawer_authority).await?;
 
     let stakes: Vec<_> = stakes
         .iter()
@@ -63,7 +148,7 @@ fn get_stakes_groupped_by_validator(
     Ok(total_stakes)
 }
 
-fn get_stake_accounts(
+async fn get_stake_accounts(
     rpc_client: &RpcClient,
     delegation_authority: &Pubkey,
     withdrawer_authority: Option<&Pubkey>,
@@ -87,19 +172,21 @@ fn get_stake_accounts(
         }));
     }
 
-    let accounts = rpc_client.get_program_accounts_with_config(
-        &stake::program::ID,
-        RpcProgramAccountsConfig {
-            filters: Some(filters),
-            account_config: RpcAccountInfoConfig {
-                encoding: Some(UiAccountEncoding::Base64),
-                commitment: Some(rpc_client.commitment()),
-                data_slice: None,
-                min_context_slot: None,
+    let accounts = rpc_client
+        .get_program_accounts_with_config(
+            &stake::program::ID,
+            RpcProgramAccountsConfig {
+                filters: Some(filters),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(rpc_client.commitment()),
+                    data_slice: None,
+                    min_context_slot: None,
+                },
+                with_context: None,
             },
-            with_context: None,
-        },
-    )?;
+        )
+        .await?;
 
     Ok(accounts
         .iter()
@@ -124,29 +211,31 @@ impl Gauge {
     pub const LEN: usize = 200;
 }
 
-pub fn get_mnde_votes(
+pub async fn get_mnde_votes(
     rpc_client: &RpcClient,
     escrow_relocker: Pubkey,
     gauge_meister: Pubkey,
 ) -> anyhow::Result<HashMap<String, u64>> {
     info!("Getting MNDE votes");
-    let accounts = rpc_client.get_program_accounts_with_config(
-        &escrow_relocker,
-        RpcProgramAccountsConfig {
-            filters: Some(vec![RpcFilterType::Memcmp(Memcmp {
-                offset: 8,
-                bytes: MemcmpEncodedBytes::Binary(gauge_meister.to_string()),
-                encoding: None,
-            })]),
-            account_config: RpcAccountInfoConfig {
-                encoding: Some(UiAccountEncoding::Base64),
-                commitment: Some(rpc_client.commitment()),
-                min_context_slot: None,
-                data_slice: None,
+    let accounts = rpc_client
+        .get_program_accounts_with_config(
+            &escrow_relocker,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![RpcFilterType::Memcmp(Memcmp {
+                    offset: 8,
+                    bytes: MemcmpEncodedBytes::Binary(gauge_meister.to_string()),
+                    encoding: None,
+                })]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(rpc_client.commitment()),
+                    min_context_slot: None,
+                    data_slice: None,
+                },
+                with_context: None,
             },
-            with_context: None,
-        },
-    )?;
+        )
+        .await?;
 
     let gauges: Vec<Gauge> = accounts
         .iter()
@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_request::RpcRequest,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcFixtureCall {
+    pub method: String,
+    pub params: Value,
+    pub response: Value,
+}
+
+/// Successful RPC calls in the order they were made
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RpcFixture {
+    pub calls: Vec<RpcFixtureCall>,
+}
+
+pub type SharedRpcFixture = Arc<Mutex<RpcFixture>>;
+
+pub fn load_rpc_fixture(path: &str) -> anyhow::Result<RpcFixture> {
+    let file = std::fs::File::open(path)?;
+
+    Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+}
+
+pub fn write_rpc_fixture(path: &str, fixture: &RpcFixture) -> anyhow::Result<()> {
+    let file = std::fs::File::create(path)?;
+    serde_json::to_writer(std::io::BufWriter::new(file), fixture)?;
+    info!("Recorded {} RPC calls to {}", fixture.calls.len(), path);
+
+    Ok(())
+}
+
+fn call_key(method: &str, params: &Value) -> String {
+    format!("{} {}", method, params)
+}
+
+/// Passes the requests to the inner sender and records the successful responses
+pub struct RecordingRpcSender<T: RpcSender> {
+    inner: T,
+    fixture: SharedRpcFixture,
+}
+
+impl<T: RpcSender> RecordingRpcSender<T> {
+    pub fn new(inner: T, fixture: SharedRpcFixture) -> Self {
+        Self { inner, fixture }
+    }
+}
+
+#[async_trait]
+impl<T: RpcSender + Send + Sync> RpcSender for RecordingRpcSender<T> {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let response = self.inner.send(request, params.clone()).await?;
+        self.fixture.lock().unwrap().calls.push(RpcFixtureCall {
+            method: request.to_string(),
+            params,
+            response: response.clone(),
+        });
+
+        Ok(response)
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.inner.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.inner.url()
+    }
+}
+
+/// Answers the requests from a recorded fixture. Calls with the same method and params
+/// get the recorded responses in the recorded order, the last one is repeated afterwards.
+pub struct ReplayRpcSender {
+    responses: HashMap<String, Vec<Value>>,
+    next_response: Mutex<HashMap<String, usize>>,
+}
+
+impl ReplayRpcSender {
+    pub fn new(fixture: RpcFixture) -> Self {
+        let mut responses: HashMap<String, Vec<Value>> = HashMap::new();
+        for call in fixture.calls {
+            responses
+                .entry(call_key(&call.method, &call.params))
+                .or_default()
+                .push(call.response);
+        }
+
+        Self {
+            responses,
+            next_response: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl RpcSender for ReplayRpcSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let key = call_key(&request.to_string(), &params);
+        let responses = self.responses.get(&key).ok_or_else(|| {
+            ClientError::from(ClientErrorKind::Custom(format!(
+                "No recorded response for RPC call {}",
+                key
+            )))
+        })?;
+
+        let mut next_response = self.next_response.lock().unwrap();
+        let index = next_response.entry(key).or_insert(0);
+        let response = responses[(*index).min(responses.len() - 1)].clone();
+        *index += 1;
+
+        Ok(response)
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        Default::default()
+    }
+
+    fn url(&self) -> String {
+        "replay".to_string()
+    }
+}
@@ -0,0 +1,271 @@
+use crate::common::CommonParams;
+use crate::rpc_fixture::{
+    load_rpc_fixture, write_rpc_fixture, RecordingRpcSender, ReplayRpcSender, SharedRpcFixture,
+};
+use async_trait::async_trait;
+use log::{error, info, warn};
+use serde::Serialize;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::RpcClientConfig,
+    rpc_request::{RpcError, RpcRequest},
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, sleep_until, Duration, Instant};
+
+// JSON-RPC errors caused by the request itself, another endpoint would fail the same way
+const NON_RETRYABLE_RPC_ERROR_CODES: [i64; 3] = [-32600, -32601, -32602];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RpcAttemptError {
+    pub url: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RpcFailedCall {
+    pub method: String,
+    /// The call succeeded after the failed attempts (retry or another endpoint)
+    pub recovered: bool,
+    pub attempts: Vec<RpcAttemptError>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct RpcReport {
+    pub failed_calls: Vec<RpcFailedCall>,
+}
+
+impl RpcReport {
+    pub fn unrecovered_calls(&self) -> usize {
+        self.failed_calls
+            .iter()
+            .filter(|call| !call.recovered)
+            .count()
+    }
+}
+
+pub type SharedRpcReport = Arc<Mutex<RpcReport>>;
+
+struct RpcEndpoint {
+    url: String,
+    client: RpcClient,
+    next_request_at: Mutex<Instant>,
+}
+
+/// Sends every request to one of the configured endpoints, failed requests are retried
+/// with exponential backoff and the next endpoint is used after each failure.
+pub struct FailoverRpcSender {
+    endpoints: Vec<RpcEndpoint>,
+    preferred_endpoint: AtomicUsize,
+    max_retries: u32,
+    backoff: Duration,
+    max_backoff: Duration,
+    min_request_interval: Option<Duration>,
+    report: SharedRpcReport,
+}
+
+fn is_retryable(err: &ClientError) -> bool {
+    match err.kind() {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => {
+            !NON_RETRYABLE_RPC_ERROR_CODES.contains(code)
+        }
+        ClientErrorKind::SigningError(_) | ClientErrorKind::TransactionError(_) => false,
+        _ => true,
+    }
+}
+
+impl FailoverRpcSender {
+    pub fn new(common_params: &CommonParams, report: SharedRpcReport) -> Self {
+        let commitment = CommitmentConfig::from_str(&common_params.commitment).unwrap();
+
+        Self {
+            endpoints: common_params
+                .rpc_urls
+                .iter()
+                .map(|url| RpcEndpoint {
+                    url: url.clone(),
+                    client: RpcClient::new_with_commitment(url.clone(), commitment),
+                    next_request_at: Mutex::new(Instant::now()),
+                })
+                .collect(),
+            preferred_endpoint: AtomicUsize::new(0),
+            max_retries: common_params.rpc_max_retries,
+            backoff: Duration::from_millis(common_params.rpc_backoff_ms),
+            max_backoff: Duration::from_millis(common_params.rpc_max_backoff_ms),
+            min_request_interval: match common_params.rpc_rate_limit {
+                0 => None,
+                rate_limit => Some(Duration::from_secs(1) / rate_limit),
+            },
+            report,
+        }
+    }
+
+    /// Waits for the next free slot of the endpoint when the rate limit is set
+    async fn wait_for_rate_limit(&self, endpoint: &RpcEndpoint) {
+        if let Some(min_request_interval) = self.min_request_interval {
+            let request_at = {
+                let mut next_request_at = endpoint.next_request_at.lock().unwrap();
+                let request_at = (*next_request_at).max(Instant::now());
+                *next_request_at = request_at + min_request_interval;
+                request_at
+            };
+            sleep_until(request_at).await;
+        }
+    }
+
+    fn record_failed_call(&self, method: String, recovered: bool, attempts: Vec<RpcAttemptError>) {
+        self.report
+            .lock()
+            .unwrap()
+            .failed_calls
+            .push(RpcFailedCall {
+                method,
+                recovered,
+                attempts,
+            });
+    }
+}
+
+#[async_trait]
+impl RpcSender for FailoverRpcSender {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> ClientResult<serde_json::Value> {
+        let method = request.to_string();
+        let mut failed_attempts: Vec<RpcAttemptError> = vec![];
+        let mut backoff = self.backoff;
+
+        loop {
+            let endpoint_index =
+                self.preferred_endpoint.load(Ordering::Relaxed) % self.endpoints.len();
+            let endpoint = &self.endpoints[endpoint_index];
+            self.wait_for_rate_limit(endpoint).await;
+
+            let err = match endpoint
+                .client
+                .send::<serde_json::Value>(request, params.clone())
+                .await
+            {
+                Ok(response) => {
+                    if !failed_attempts.is_empty() {
+                        info!(
+                            "RPC call {} succeeded on {} after {} failed attempts",
+                            method,
+                            endpoint.url,
+                            failed_attempts.len()
+                        );
+                        self.record_failed_call(method, true, failed_attempts);
+                    }
+                    return Ok(response);
+                }
+                Err(err) => err,
+            };
+
+            failed_attempts.push(RpcAttemptError {
+                url: endpoint.url.clone(),
+                error: err.to_string(),
+            });
+
+            if !is_retryable(&err) || failed_attempts.len() > self.max_retries as usize {
+                error!(
+                    "RPC call {} failed after {} attempts: {}",
+                    method,
+                    failed_attempts.len(),
+                    err
+                );
+                self.record_failed_call(method, false, failed_attempts);
+                return Err(err);
+            }
+
+            // Fail over to the next endpoint, other concurrent calls follow
+            let _ = self.preferred_endpoint.compare_exchange(
+                endpoint_index,
+                endpoint_index + 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+            warn!(
+                "RPC call {} failed on {}, retrying in {:?}: {}",
+                method, endpoint.url, backoff, err
+            );
+            sleep(backoff).await;
+            backoff = (backoff * 2).min(self.max_backoff);
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        Default::default()
+    }
+
+    fn url(&self) -> String {
+        self.endpoints
+            .iter()
+            .map(|endpoint| endpoint.url.clone())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// State shared with the RPC client, written out by `write_rpc_session` once the calls are done
+pub struct RpcSession {
+    pub report: SharedRpcReport,
+    pub recording: Option<SharedRpcFixture>,
+}
+
+/// Creates the failover RPC client, the calls are recorded or replayed when set in the params
+pub fn rpc_client(common_params: &CommonParams) -> anyhow::Result<(RpcClient, RpcSession)> {
+    let session = RpcSession {
+        report: Default::default(),
+        recording: common_params
+            .rpc_record_path
+            .as_ref()
+            .map(|_| Default::default()),
+    };
+    let config =
+        RpcClientConfig::with_commitment(CommitmentConfig::from_str(&common_params.commitment)?);
+
+    let client = if let Some(replay_path) = &common_params.rpc_replay_path {
+        info!("Replaying RPC calls from {}", replay_path);
+        RpcClient::new_sender(ReplayRpcSender::new(load_rpc_fixture(replay_path)?), config)
+    } else {
+        let sender = FailoverRpcSender::new(common_params, session.report.clone());
+        match &session.recording {
+            Some(recording) => {
+                RpcClient::new_sender(RecordingRpcSender::new(sender, recording.clone()), config)
+            }
+            None => RpcClient::new_sender(sender, config),
+        }
+    };
+
+    Ok((client, session))
+}
+
+/// Writes the failed RPC calls to the log and the report file (if set), the calls are removed from the report.
+/// The recorded calls are written to the record file.
+pub fn write_rpc_session(common_params: &CommonParams, session: &RpcSession) -> anyhow::Result<()> {
+    let report = std::mem::take(&mut *session.report.lock().unwrap());
+    if !report.failed_calls.is_empty() {
+        warn!(
+            "RPC calls with failed attempts: {}, unrecovered: {}",
+            report.failed_calls.len(),
+            report.unrecovered_calls()
+        );
+    }
+    if let Some(report_path) = &common_params.rpc_report_path {
+        serde_yaml::to_writer(std::fs::File::create(report_path)?, &report)?;
+    }
+    if let (Some(record_path), Some(recording)) =
+        (&common_params.rpc_record_path, &session.recording)
+    {
+        write_rpc_fixture(record_path, &recording.lock().unwrap())?;
+    }
+
+    Ok(())
+}
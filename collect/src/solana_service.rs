@@ -1,8 +1,12 @@
 use crate::validators::*;
 use bincode::deserialize;
+use futures::{stream, StreamExt, TryStreamExt};
 use log::{error, info};
 use serde_json::{Map, Value};
-use solana_client::{rpc_client::RpcClient, rpc_response::RpcVoteAccountStatus};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_response::{RpcContactInfo, RpcVoteAccountStatus},
+};
 use solana_config_program::{get_config_data, ConfigKeys};
 use solana_sdk::{
     account::from_account,
@@ -17,14 +21,10 @@ use std::{
     str::FromStr,
 };
 
-pub fn solana_client(url: String, commitment: String) -> RpcClient {
-    RpcClient::new_with_commitment(url, CommitmentConfig::from_str(&commitment).unwrap())
-}
-
-pub fn get_credits(rpc_client: &RpcClient, epoch: Epoch) -> anyhow::Result<HashMap<String, u64>> {
-    info!("Getting credits");
-    let vote_accounts = rpc_client.get_vote_accounts()?;
+/// How many requests of one kind (e.g. chunks of inflation rewards) are sent concurrently
+pub const RPC_CONCURRENCY: usize = 10;
 
+pub fn get_credits(vote_accounts: &RpcVoteAccountStatus, epoch: Epoch) -> HashMap<String, u64> {
     let mut credits = HashMap::new();
 
     for vote_account in vote_accounts
@@ -42,35 +42,27 @@ pub fn get_credits(rpc_client: &RpcClient, epoch: Epoch) -> anyhow::Result<HashM
         }
     }
 
-    Ok(credits)
+    credits
 }
 
-pub fn get_cluster_nodes_versions(
-    rpc_client: &RpcClient,
-) -> anyhow::Result<HashMap<String, String>> {
-    info!("Getting cluster nodes versions");
-    let cluster_nodes = rpc_client.get_cluster_nodes()?;
-
-    Ok(cluster_nodes
+pub fn get_cluster_nodes_versions(cluster_nodes: &[RpcContactInfo]) -> HashMap<String, String> {
+    cluster_nodes
         .iter()
         .filter_map(|node| match &node.version {
             Some(version) => Some((node.pubkey.clone(), version.clone())),
             _ => None,
         })
-        .collect())
+        .collect()
 }
 
-pub fn get_cluster_nodes_ips(rpc_client: &RpcClient) -> anyhow::Result<HashMap<String, String>> {
-    info!("Getting cluster nodes IPs");
-    let cluster_nodes = rpc_client.get_cluster_nodes()?;
-
-    Ok(cluster_nodes
+pub fn get_cluster_nodes_ips(cluster_nodes: &[RpcContactInfo]) -> HashMap<String, String> {
+    cluster_nodes
         .iter()
         .filter_map(|node| match &node.gossip {
             Some(gossip) => Some((node.pubkey.clone(), gossip.ip().to_string())),
             _ => None,
         })
-        .collect())
+        .collect()
 }
 
 pub fn get_total_activated_stake(vote_accounts: &RpcVoteAccountStatus) -> (u64, u64) {
@@ -112,29 +104,32 @@ pub fn get_minimum_superminority_stake(vote_accounts: &RpcVoteAccountStatus) ->
     last_stake
 }
 
-pub fn get_block_production_by_validator(
+pub async fn get_block_production_by_validator(
     rpc_client: &RpcClient,
     epoch: Epoch,
 ) -> anyhow::Result<HashMap<String, (usize, usize)>> {
     info!("Getting block production by validator");
-    let epoch_schedule = rpc_client.get_epoch_schedule()?;
+    let (epoch_schedule, current_epoch_production) = futures::try_join!(
+        rpc_client.get_epoch_schedule(),
+        rpc_client.get_block_production()
+    )?;
     let first_slot_in_epoch = epoch_schedule.get_first_slot_in_epoch(epoch);
     let last_slot_in_epoch = epoch_schedule.get_last_slot_in_epoch(epoch);
 
-    let current_epoch_production = rpc_client.get_block_production()?;
     if first_slot_in_epoch == current_epoch_production.value.range.first_slot {
         return Ok(current_epoch_production.value.by_identity);
     }
 
+    // Old epochs must fail with SlotHistoryRangeError, their leader schedule is gone as well
     let confirmed_blocks =
-        get_confirmed_blocks(rpc_client, first_slot_in_epoch, last_slot_in_epoch)?;
-
+        get_confirmed_blocks(rpc_client, first_slot_in_epoch, last_slot_in_epoch).await?;
     let leader_schedule = rpc_client
         .get_leader_schedule_with_commitment(
             Some(first_slot_in_epoch),
             CommitmentConfig::finalized(), // todo take from config
-        )?
-        .unwrap();
+        )
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Leader schedule of epoch {} is not available", epoch))?;
 
     let mut blocks_and_slots = HashMap::new();
     for (validator_identity, relative_slots) in leader_schedule {
@@ -157,7 +152,27 @@ pub fn get_block_production_by_validator(
     Ok(blocks_and_slots)
 }
 
-fn get_confirmed_blocks(
+#[derive(Debug)]
+pub struct SlotHistoryRangeError {
+    pub start_slot: Slot,
+    pub end_slot: Slot,
+    pub oldest_slot: Slot,
+    pub newest_slot: Slot,
+}
+
+impl std::fmt::Display for SlotHistoryRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "slot range [{},{}] is not within the SlotHistory sysvar [{},{}]",
+            self.start_slot, self.end_slot, self.oldest_slot, self.newest_slot
+        )
+    }
+}
+
+impl std::error::Error for SlotHistoryRangeError {}
+
+async fn get_confirmed_blocks(
     rpc_client: &RpcClient,
     start_slot: Slot,
     end_slot: Slot,
@@ -167,11 +182,13 @@ fn get_confirmed_blocks(
         start_slot, end_slot
     );
     let slot_history_account = rpc_client
-        .get_account_with_commitment(&sysvar::slot_history::id(), CommitmentConfig::finalized())?
+        .get_account_with_commitment(&sysvar::slot_history::id(), CommitmentConfig::finalized())
+        .await?
         .value
-        .unwrap();
+        .ok_or_else(|| anyhow::anyhow!("SlotHistory sysvar account not found"))?;
 
-    let slot_history: SlotHistory = from_account(&slot_history_account).unwrap();
+    let slot_history: SlotHistory = from_account(&slot_history_account)
+        .ok_or_else(|| anyhow::anyhow!("Failed to deserialize the SlotHistory sysvar"))?;
 
     if start_slot >= slot_history.oldest() && end_slot <= slot_history.newest() {
         info!("slot range within the SlotHistory sysvar");
@@ -179,7 +196,13 @@ fn get_confirmed_blocks(
             .filter(|slot| slot_history.check(*slot) == slot_history::Check::Found)
             .collect())
     } else {
-        anyhow::bail!("slot range is not within the SlotHistory sysvar")
+        Err(SlotHistoryRangeError {
+            start_slot,
+            end_slot,
+            oldest_slot: slot_history.oldest(),
+            newest_slot: slot_history.newest(),
+        }
+        .into())
     }
 }
 
@@ -208,11 +231,13 @@ fn parse_validator_info(
         anyhow::bail!("{} could not be parsed as a validator info account", pubkey);
     }
 }
-pub fn get_validators_info(
+pub async fn get_validators_info(
     rpc_client: &RpcClient,
 ) -> anyhow::Result<HashMap<String, ValidatorInfo>> {
     info!("Getting validator info");
-    let validator_info = rpc_client.get_program_accounts(&solana_config_program::id())?;
+    let validator_info = rpc_client
+        .get_program_accounts(&solana_config_program::id())
+        .await?;
 
     let mut validator_info_map = HashMap::new();
     if validator_info.is_empty() {
@@ -236,15 +261,20 @@ fn extract_json_value(json: &Map<String, Value>, key: String) -> Option<String>
         .flatten()
 }
 
-pub fn get_apy(
+pub async fn get_apy(
     rpc_client: &RpcClient,
     vote_accounts: &RpcVoteAccountStatus,
     credits: &HashMap<String, u64>,
 ) -> anyhow::Result<HashMap<String, f64>> {
     info!("Calculating APY");
-    let supply = rpc_client.supply()?.value.total;
-    let inflation = rpc_client.get_inflation_rate()?.total;
-    let inflation_taper = rpc_client.get_inflation_governor()?.taper;
+    let (supply, inflation, inflation_governor) = futures::try_join!(
+        rpc_client.supply(),
+        rpc_client.get_inflation_rate(),
+        rpc_client.get_inflation_governor()
+    )?;
+    let supply = supply.value.total;
+    let inflation = inflation.total;
+    let inflation_taper = inflation_governor.taper;
 
     let epochs_in_year = 160; // @todo fix
 
@@ -297,7 +327,7 @@ pub fn get_apy(
     Ok(apy)
 }
 
-pub fn get_commission_from_inflation_rewards(
+pub async fn get_commission_from_inflation_rewards(
     rpc_client: &RpcClient,
     vote_accounts: &RpcVoteAccountStatus,
     epoch: Option<Epoch>,
@@ -308,9 +338,19 @@ pub fn get_commission_from_inflation_rewards(
         .chain(vote_accounts.delinquent.iter())
         .map(|v| Pubkey::from_str(&v.vote_pubkey).unwrap())
         .collect();
+    let rewards_by_chunk: Vec<_> = stream::iter(vote_addresses.chunks(100))
+        .map(|vote_addresses_chunk| async move {
+            let rewards = rpc_client
+                .get_inflation_reward(vote_addresses_chunk, epoch)
+                .await?;
+            Ok::<_, anyhow::Error>((vote_addresses_chunk, rewards))
+        })
+        .buffer_unordered(RPC_CONCURRENCY)
+        .try_collect()
+        .await?;
+
     let mut result: HashMap<String, u8> = Default::default();
-    for vote_addresses_chunk in vote_addresses.chunks(100) {
-        let rewards = rpc_client.get_inflation_reward(vote_addresses_chunk, epoch)?;
+    for (vote_addresses_chunk, rewards) in rewards_by_chunk {
         result.extend(vote_addresses_chunk.iter().zip(rewards).filter_map(
             |(vote_address, reward)| {
                 if let Some(reward) = reward {
@@ -1,11 +1,12 @@
 use crate::common::*;
 use crate::marinade_service::*;
-use crate::solana_service::solana_client;
+use crate::rpc_service::{rpc_client, write_rpc_session};
 use crate::solana_service::*;
 use crate::validators_performance::{validators_performance, ValidatorPerformance};
 use crate::whois_service::*;
 use log::info;
 use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::clock::Epoch;
 use solana_sdk::pubkey::Pubkey;
 use structopt::StructOpt;
@@ -13,22 +14,22 @@ use structopt::StructOpt;
 #[derive(Debug, StructOpt)]
 pub struct ValidatorsOptions {
     #[structopt(long = "gauge-meister", help = "Gauge meister of the vote gauges.")]
-    gauge_meister: Option<Pubkey>,
+    pub gauge_meister: Option<Pubkey>,
 
     #[structopt(long = "escrow-relocker", help = "Escrow relocker program address.")]
-    escrow_relocker: Option<Pubkey>,
+    pub escrow_relocker: Option<Pubkey>,
 
-    #[structopt(long = "whois", help = "Base URL for whois API.")]
-    whois: Option<String>,
+    #[structopt(flatten)]
+    pub ip_info: IpInfoOptions,
 
     #[structopt(
-        long = "whois-bearer-token",
-        help = "Bearer token to be used to fetch data from whois API"
+        long = "marinade-config",
+        help = "Marinade state and stake authorities, mainnet is used by default."
     )]
-    whois_bearer_token: Option<String>,
+    pub marinade_config_path: Option<String>,
 
     #[structopt(long = "epoch", help = "Which epoch to use for epoch-based metrics.")]
-    epoch: Option<Epoch>,
+    pub epoch: Option<Epoch>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -90,15 +91,40 @@ pub struct Snapshot {
     pub validators: Vec<ValidatorSnapshot>,
 }
 
-pub fn collect_validators_info(
-    common_params: CommonParams,
-    options: ValidatorsOptions,
-) -> anyhow::Result<()> {
-    info!("Collecting snaphost of validators: {:?}", &options);
-    let client = solana_client(common_params.rpc_url, common_params.commitment);
-
+pub async fn validators_snapshot(
+    client: &RpcClient,
+    options: &ValidatorsOptions,
+) -> anyhow::Result<Snapshot> {
     let created_at = chrono::Utc::now();
-    let current_epoch_info = client.get_epoch_info()?;
+    let marinade_authorities = get_marinade_authorities(
+        client,
+        &load_marinade_config(&options.marinade_config_path)?,
+    )
+    .await?;
+    let (
+        current_epoch_info,
+        vote_accounts,
+        cluster_nodes,
+        marinade_stake,
+        decentralizer_stake,
+        validators_info,
+        mnde_votes,
+    ) = futures::try_join!(
+        async { Ok::<_, anyhow::Error>(client.get_epoch_info().await?) },
+        async { Ok(client.get_vote_accounts().await?) },
+        async { Ok(client.get_cluster_nodes().await?) },
+        get_marinade_stakes(client, &marinade_authorities),
+        get_decentralizer_stakes(client, &marinade_authorities),
+        get_validators_info(client),
+        async {
+            match (options.escrow_relocker, options.gauge_meister) {
+                (Some(escrow_relocker), Some(gauge_meister)) => Ok(Some(
+                    get_mnde_votes(client, escrow_relocker, gauge_meister).await?,
+                )),
+                _ => Ok(None),
+            }
+        }
+    )?;
     info!("Current epoch: {:?}", current_epoch_info);
 
     let epoch = options.epoch.unwrap_or(current_epoch_info.epoch);
@@ -106,7 +132,6 @@ pub fn collect_validators_info(
 
     let mut validators: Vec<ValidatorSnapshot> = vec![];
 
-    let vote_accounts = client.get_vote_accounts()?;
     info!(
         "Total vote accounts found: {}",
         vote_accounts.current.len() + vote_accounts.delinquent.len()
@@ -128,28 +153,25 @@ pub fn collect_validators_info(
     );
 
     let minimum_superminority_stake = get_minimum_superminority_stake(&vote_accounts);
-    let marinade_stake = get_marinade_stakes(&client)?;
-    let decentralizer_stake = get_decentralizer_stakes(&client)?;
-
-    let validators_info = get_validators_info(&client)?;
-    let mnde_votes = if let (Some(escrow_relocker), Some(gauge_meister)) =
-        (options.escrow_relocker, options.gauge_meister)
-    {
-        Some(get_mnde_votes(&client, escrow_relocker, gauge_meister)?)
-    } else {
-        None
-    };
-    let node_ips = get_cluster_nodes_ips(&client)?;
-
-    let data_centers = match options.whois {
-        Some(whois) => get_data_centers(
-            WhoisClient::new(whois, options.whois_bearer_token),
-            node_ips.clone(),
-        )?,
-        _ => Default::default(),
-    };
-
-    let performance = validators_performance(&client, epoch, &vote_accounts)?;
+    let node_ips = get_cluster_nodes_ips(&cluster_nodes);
+    let ip_info_source = IpInfoSource::from_options(&options.ip_info)?;
+
+    let (data_centers, performance) = futures::try_join!(
+        async {
+            match &ip_info_source {
+                Some(ip_info_source) => {
+                    get_data_centers(
+                        ip_info_source,
+                        &mut IpInfoCache::from_options(&options.ip_info),
+                        node_ips.clone(),
+                    )
+                    .await
+                }
+                _ => Ok(Default::default()),
+            }
+        },
+        validators_performance(client, epoch, &vote_accounts, &cluster_nodes)
+    )?;
 
     for vote_account in vote_accounts
         .current
@@ -199,14 +221,23 @@ pub fn collect_validators_info(
         });
     }
 
-    serde_yaml::to_writer(
-        std::io::stdout(),
-        &Snapshot {
-            epoch,
-            created_at: created_at.to_string(),
-            validators,
-        },
-    )?;
+    Ok(Snapshot {
+        epoch,
+        created_at: created_at.to_string(),
+        validators,
+    })
+}
+
+pub async fn collect_validators_info(
+    common_params: CommonParams,
+    options: ValidatorsOptions,
+) -> anyhow::Result<()> {
+    info!("Collecting snaphost of validators: {:?}", &options);
+    let (client, rpc_session) = rpc_client(&common_params)?;
+
+    let snapshot = validators_snapshot(&client, &options).await;
+    write_rpc_session(&common_params, &rpc_session)?;
+    serde_yaml::to_writer(std::io::stdout(), &snapshot?)?;
 
     Ok(())
 }
@@ -1,12 +1,12 @@
 use crate::common::*;
-use crate::solana_service::solana_client;
+use crate::rpc_service::{rpc_client, write_rpc_session};
 use anchor_lang::AccountDeserialize;
 use log::info;
 use serde::{Deserialize, Serialize};
 use serde_yaml;
 use solana_account_decoder::UiAccountEncoding;
 use solana_client::{
-    rpc_client::RpcClient,
+    nonblocking::rpc_client::RpcClient,
     rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
     rpc_filter::RpcFilterType,
 };
@@ -33,7 +33,7 @@ pub struct Snapshot {
     pub validators: HashMap<String, ValidatorMEVSnapshot>,
 }
 
-pub fn validators_mev(
+pub async fn validators_mev(
     client: &RpcClient,
     epoch: Epoch,
 ) -> anyhow::Result<HashMap<String, ValidatorMEVSnapshot>> {
@@ -52,8 +52,9 @@ pub fn validators_mev(
         },
         with_context: None,
     };
-    let validators_tip_distribution_accounts =
-        client.get_program_accounts_with_config(&jito_program, config)?;
+    let validators_tip_distribution_accounts = client
+        .get_program_accounts_with_config(&jito_program, config)
+        .await?;
     for validator_tip_distribution_account in validators_tip_distribution_accounts {
         let fetched_tip_distribution_account = TipDistributionAccount::try_deserialize(
             &mut validator_tip_distribution_account.1.data.as_slice(),
@@ -84,27 +85,30 @@ pub fn validators_mev(
     Ok(validators)
 }
 
-pub fn collect_validators_mev_info(common_params: CommonParams) -> anyhow::Result<()> {
-    info!("Collecting snaphost of validators MEV");
-    let client = solana_client(common_params.rpc_url, common_params.commitment);
-
+pub async fn validators_mev_snapshot(client: &RpcClient) -> anyhow::Result<Snapshot> {
     let created_at = chrono::Utc::now();
-    let current_epoch_info = client.get_epoch_info()?;
+    let current_epoch_info = client.get_epoch_info().await?;
     let epoch = current_epoch_info.epoch;
     info!("Current epoch: {:?}", current_epoch_info);
     info!("Looking at epoch: {}", epoch - 1);
 
-    let validators = validators_mev(&client, epoch)?;
+    let validators = validators_mev(client, epoch).await?;
 
-    serde_yaml::to_writer(
-        std::io::stdout(),
-        &Snapshot {
-            epoch,
-            epoch_slot: current_epoch_info.slot_index,
-            created_at: created_at.to_string(),
-            validators,
-        },
-    )?;
+    Ok(Snapshot {
+        epoch,
+        epoch_slot: current_epoch_info.slot_index,
+        created_at: created_at.to_string(),
+        validators,
+    })
+}
+
+pub async fn collect_validators_mev_info(common_params: CommonParams) -> anyhow::Result<()> {
+    info!("Collecting snaphost of validators MEV");
+    let (client, rpc_session) = rpc_client(&common_params)?;
+
+    let snapshot = validators_mev_snapshot(&client).await;
+    write_rpc_session(&common_params, &rpc_session)?;
+    serde_yaml::to_writer(std::io::stdout(), &snapshot?)?;
 
     Ok(())
 }
@@ -1,10 +1,13 @@
 use crate::common::*;
-use crate::solana_service::solana_client;
+use crate::rpc_service::{rpc_client, write_rpc_session};
 use crate::solana_service::*;
 use log::info;
 use serde::{Deserialize, Serialize};
 use serde_yaml;
-use solana_client::{rpc_client::RpcClient, rpc_response::RpcVoteAccountStatus};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_response::{RpcContactInfo, RpcVoteAccountStatus},
+};
 use solana_sdk::clock::Epoch;
 use std::collections::{HashMap, HashSet};
 use structopt::StructOpt;
@@ -12,10 +15,10 @@ use structopt::StructOpt;
 #[derive(Debug, StructOpt)]
 pub struct ValidatorsPerformanceOptions {
     #[structopt(long = "with-rewards", help = "Whether to calculate APY and rewards.")]
-    with_rewards: bool,
+    pub with_rewards: bool,
 
     #[structopt(long = "epoch", help = "Which epoch to use for epoch-based metrics.")]
-    epoch: Option<Epoch>,
+    pub epoch: Option<Epoch>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -39,12 +42,19 @@ pub struct ValidatorPerformance {
     pub blocks_produced: usize,
     pub skip_rate: f64,
     pub delinquent: bool,
+    #[serde(default)]
+    pub last_vote: u64,
+    #[serde(default)]
+    pub root_slot: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ValidatorsPerformanceSnapshot {
     pub epoch: Epoch,
     pub epoch_slot: u64,
+    /// Absolute slot the vote slots of the validators are compared to, 0 in older snapshots
+    #[serde(default)]
+    pub slot: u64,
     pub transaction_count: u64,
     pub created_at: String,
     pub cluster_inflation: Option<ClusterInflation>,
@@ -52,10 +62,11 @@ pub struct ValidatorsPerformanceSnapshot {
     pub rewards: Option<HashMap<String, ValidatorRewards>>,
 }
 
-pub fn validators_performance(
+pub async fn validators_performance(
     client: &RpcClient,
     epoch: Epoch,
     vote_accounts: &RpcVoteAccountStatus,
+    cluster_nodes: &[RpcContactInfo],
 ) -> anyhow::Result<HashMap<String, ValidatorPerformance>> {
     let mut validators: HashMap<String, ValidatorPerformance> = Default::default();
 
@@ -64,9 +75,9 @@ pub fn validators_performance(
         .iter()
         .map(|v| v.vote_pubkey.clone())
         .collect();
-    let production_by_validator = get_block_production_by_validator(&client, epoch)?;
-    let node_versions = get_cluster_nodes_versions(&client)?;
-    let credits = get_credits(&client, epoch)?;
+    let production_by_validator = get_block_production_by_validator(client, epoch).await?;
+    let node_versions = get_cluster_nodes_versions(cluster_nodes);
+    let credits = get_credits(vote_accounts, epoch);
 
     for vote_account in vote_accounts
         .current
@@ -94,6 +105,8 @@ pub fn validators_performance(
                     1f64 - (blocks_produced as f64 / leader_slots as f64)
                 },
                 delinquent: delinquent.contains(&vote_pubkey),
+                last_vote: vote_account.last_vote,
+                root_slot: vote_account.root_slot,
             },
         );
     }
@@ -101,13 +114,13 @@ pub fn validators_performance(
     Ok(validators)
 }
 
-pub fn validator_rewards(
+pub async fn validator_rewards(
     client: &RpcClient,
     epoch: Epoch,
     vote_accounts: &RpcVoteAccountStatus,
 ) -> anyhow::Result<HashMap<String, ValidatorRewards>> {
     let commission_from_rewards =
-        get_commission_from_inflation_rewards(&client, &vote_accounts, Some(epoch))?;
+        get_commission_from_inflation_rewards(client, vote_accounts, Some(epoch)).await?;
 
     Ok(vote_accounts
         .current
@@ -126,20 +139,34 @@ pub fn validator_rewards(
         .collect())
 }
 
-pub fn collect_validators_performance_info(
-    common_params: CommonParams,
-    options: ValidatorsPerformanceOptions,
-) -> anyhow::Result<()> {
-    info!("Collecting snaphost of validators' performance");
-    let client = solana_client(common_params.rpc_url, common_params.commitment);
+async fn cluster_inflation(client: &RpcClient) -> anyhow::Result<ClusterInflation> {
+    let (supply, inflation, inflation_governor) = futures::try_join!(
+        client.supply(),
+        client.get_inflation_rate(),
+        client.get_inflation_governor()
+    )?;
 
+    Ok(ClusterInflation {
+        sol_total_supply: supply.value.total,
+        inflation: inflation.total,
+        inflation_taper: inflation_governor.taper,
+    })
+}
+
+pub async fn validators_performance_snapshot(
+    client: &RpcClient,
+    options: &ValidatorsPerformanceOptions,
+) -> anyhow::Result<ValidatorsPerformanceSnapshot> {
     let created_at = chrono::Utc::now();
-    let current_epoch_info = client.get_epoch_info()?;
+    let (current_epoch_info, vote_accounts, cluster_nodes) = futures::try_join!(
+        client.get_epoch_info(),
+        client.get_vote_accounts(),
+        client.get_cluster_nodes()
+    )?;
     let epoch = options.epoch.unwrap_or(current_epoch_info.epoch);
     info!("Current epoch: {:?}", current_epoch_info);
     info!("Looking at epoch: {}", epoch);
 
-    let vote_accounts = client.get_vote_accounts()?;
     info!(
         "Total vote accounts found: {}",
         vote_accounts.current.len() + vote_accounts.delinquent.len()
@@ -149,40 +176,48 @@ pub fn collect_validators_performance_info(
         vote_accounts.delinquent.len()
     );
 
-    let validators = validators_performance(&client, epoch, &vote_accounts)?;
+    let (validators, rewards, cluster_inflation) = futures::try_join!(
+        validators_performance(client, epoch, &vote_accounts, &cluster_nodes),
+        async {
+            match options.with_rewards {
+                true => Ok(Some(
+                    validator_rewards(client, epoch, &vote_accounts).await?,
+                )),
+                false => Ok(None),
+            }
+        },
+        async {
+            match options.with_rewards {
+                true => Ok(Some(cluster_inflation(client).await?)),
+                false => Ok(None),
+            }
+        }
+    )?;
 
-    let rewards = if options.with_rewards {
-        Some(validator_rewards(&client, epoch, &vote_accounts)?)
-    } else {
-        None
-    };
+    Ok(ValidatorsPerformanceSnapshot {
+        epoch,
+        epoch_slot: current_epoch_info.slot_index,
+        slot: current_epoch_info.absolute_slot,
+        transaction_count: current_epoch_info
+            .transaction_count
+            .ok_or_else(|| anyhow::anyhow!("Transaction count missing in the epoch info"))?,
+        created_at: created_at.to_string(),
+        cluster_inflation,
+        validators,
+        rewards,
+    })
+}
 
-    let cluster_inflation = if options.with_rewards {
-        let sol_total_supply = client.supply()?.value.total;
-        let inflation = client.get_inflation_rate()?.total;
-        let inflation_taper = client.get_inflation_governor()?.taper;
+pub async fn collect_validators_performance_info(
+    common_params: CommonParams,
+    options: ValidatorsPerformanceOptions,
+) -> anyhow::Result<()> {
+    info!("Collecting snaphost of validators' performance");
+    let (client, rpc_session) = rpc_client(&common_params)?;
 
-        Some(ClusterInflation {
-            sol_total_supply,
-            inflation,
-            inflation_taper,
-        })
-    } else {
-        None
-    };
-
-    serde_yaml::to_writer(
-        std::io::stdout(),
-        &ValidatorsPerformanceSnapshot {
-            epoch,
-            epoch_slot: current_epoch_info.slot_index,
-            transaction_count: current_epoch_info.transaction_count.unwrap(),
-            created_at: created_at.to_string(),
-            cluster_inflation,
-            validators,
-            rewards,
-        },
-    )?;
+    let snapshot = validators_performance_snapshot(&client, &options).await;
+    write_rpc_session(&common_params, &rpc_session)?;
+    serde_yaml::to_writer(std::io::stdout(), &snapshot?)?;
 
     Ok(())
 }
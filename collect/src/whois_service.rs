@@ -1,13 +1,55 @@
-use log::{debug, error, info};
-use serde::Deserialize;
-use std::collections::HashMap;
+use futures::{stream, StreamExt};
+use log::{debug, error, info, warn};
+use maxminddb::{geoip2, MaxMindDBError, Reader};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use structopt::StructOpt;
 
-#[derive(Deserialize, Clone)]
+#[derive(Debug, Clone, StructOpt)]
+pub struct IpInfoOptions {
+    #[structopt(long = "whois", help = "Base URL for whois API.")]
+    pub whois: Option<String>,
+
+    #[structopt(
+        long = "whois-bearer-token",
+        env = "WHOIS_BEARER_TOKEN",
+        help = "Bearer token to be used to fetch data from whois API"
+    )]
+    pub whois_bearer_token: Option<String>,
+
+    #[structopt(
+        long = "geoip-city-db",
+        help = "MaxMind City database (e.g. GeoLite2-City.mmdb) to be used instead of the whois API."
+    )]
+    pub geoip_city_db: Option<String>,
+
+    #[structopt(
+        long = "geoip-asn-db",
+        help = "MaxMind ASN database (e.g. GeoLite2-ASN.mmdb) to be used with the City database."
+    )]
+    pub geoip_asn_db: Option<String>,
+
+    #[structopt(
+        long = "ip-info-cache-file",
+        help = "File to cache the fetched info about IPs in between the runs."
+    )]
+    pub ip_info_cache_path: Option<String>,
+
+    #[structopt(
+        long = "ip-info-cache-ttl",
+        default_value = "604800",
+        help = "Seconds after which the cached info about an IP is fetched again."
+    )]
+    pub ip_info_cache_ttl: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Coordinates {
     pub lat: f64,
     pub lon: f64,
 }
-#[derive(Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct IpInfo {
     pub asn: Option<u32>,
     pub aso: Option<String>,
@@ -18,19 +60,26 @@ pub struct IpInfo {
     pub city: Option<String>,
 }
 
+const WHOIS_CONCURRENCY: usize = 20;
+
 pub struct WhoisClient {
     host: String,
     bearer_token: Option<String>,
+    client: reqwest::Client,
 }
 impl WhoisClient {
     pub fn new(host: String, bearer_token: Option<String>) -> Self {
-        Self { host, bearer_token }
+        Self {
+            host,
+            bearer_token,
+            client: reqwest::Client::new(),
+        }
     }
 
-    pub fn get_ip_info(&self, ip: &String) -> anyhow::Result<IpInfo> {
+    pub async fn get_ip_info(&self, ip: &String) -> anyhow::Result<IpInfo> {
         debug!("Fetching info about data centers: {}", &ip);
-        let client = reqwest::blocking::Client::builder().build()?;
-        let body = client
+        let body = self
+            .client
             .get(format!("{}/ip/{}", self.host.clone(), ip))
             .header(
                 "Authorization",
@@ -39,28 +88,217 @@ impl WhoisClient {
                     self.bearer_token.clone().unwrap_or("none".to_string())
                 ),
             )
-            .send()?;
-        Ok(body.json()?)
+            .send()
+            .await?;
+        Ok(body.json().await?)
+    }
+}
+
+fn english_name(names: Option<BTreeMap<&str, &str>>) -> Option<String> {
+    names.and_then(|names| names.get("en").map(|name| name.to_string()))
+}
+
+pub struct GeoIpDatabase {
+    city: Reader<Vec<u8>>,
+    asn: Option<Reader<Vec<u8>>>,
+}
+impl GeoIpDatabase {
+    pub fn open(city_db_path: &str, asn_db_path: Option<&str>) -> anyhow::Result<Self> {
+        Ok(Self {
+            city: Reader::open_readfile(city_db_path)?,
+            asn: match asn_db_path {
+                Some(asn_db_path) => Some(Reader::open_readfile(asn_db_path)?),
+                None => None,
+            },
+        })
+    }
+
+    pub fn get_ip_info(&self, ip: &str) -> anyhow::Result<IpInfo> {
+        let ip: IpAddr = ip.parse()?;
+        let city: geoip2::City = self.city.lookup(ip)?;
+        // IPs missing in the ASN database keep the data center from the City database
+        let asn: Option<geoip2::Asn> = match &self.asn {
+            Some(asn) => match asn.lookup(ip) {
+                Ok(asn) => Some(asn),
+                Err(MaxMindDBError::AddressNotFoundError(_)) => None,
+                Err(err) => return Err(err.into()),
+            },
+            None => None,
+        };
+
+        Ok(IpInfo {
+            asn: asn.as_ref().and_then(|asn| asn.autonomous_system_number),
+            aso: asn
+                .as_ref()
+                .and_then(|asn| asn.autonomous_system_organization)
+                .map(|aso| aso.to_string()),
+            coordinates: city.location.and_then(|location| {
+                Some(Coordinates {
+                    lat: location.latitude?,
+                    lon: location.longitude?,
+                })
+            }),
+            continent: city
+                .continent
+                .and_then(|continent| english_name(continent.names)),
+            country_iso: city
+                .country
+                .as_ref()
+                .and_then(|country| country.iso_code)
+                .map(|iso_code| iso_code.to_string()),
+            country: city.country.and_then(|country| english_name(country.names)),
+            city: city.city.and_then(|city| english_name(city.names)),
+        })
+    }
+}
+
+pub enum IpInfoSource {
+    Whois(WhoisClient),
+    GeoIp(Box<GeoIpDatabase>),
+}
+impl IpInfoSource {
+    /// The local database is preferred over the whois API, None if neither is set
+    pub fn from_options(options: &IpInfoOptions) -> anyhow::Result<Option<Self>> {
+        if let Some(city_db_path) = &options.geoip_city_db {
+            info!("Using the GeoIP database: {}", city_db_path);
+            return Ok(Some(Self::GeoIp(Box::new(GeoIpDatabase::open(
+                city_db_path,
+                options.geoip_asn_db.as_deref(),
+            )?))));
+        }
+
+        Ok(options.whois.as_ref().map(|whois| {
+            Self::Whois(WhoisClient::new(
+                whois.clone(),
+                options.whois_bearer_token.clone(),
+            ))
+        }))
+    }
+
+    pub async fn get_ip_info(&self, ip: &String) -> anyhow::Result<IpInfo> {
+        match self {
+            Self::Whois(whois_client) => whois_client.get_ip_info(ip).await,
+            Self::GeoIp(database) => database.get_ip_info(ip),
+        }
     }
 }
 
-pub fn get_data_centers(
-    whois_client: WhoisClient,
+#[derive(Serialize, Deserialize, Clone)]
+struct CachedIpInfo {
+    fetched_at: i64,
+    info: IpInfo,
+}
+
+#[derive(Default)]
+pub struct IpInfoCache {
+    path: Option<String>,
+    ttl: i64,
+    entries: HashMap<String, CachedIpInfo>,
+}
+impl IpInfoCache {
+    /// Missing or unreadable cache file starts an empty cache
+    pub fn load(path: String, ttl: i64) -> Self {
+        let entries = match std::fs::File::open(&path) {
+            Ok(file) => {
+                serde_json::from_reader(std::io::BufReader::new(file)).unwrap_or_else(|err| {
+                    warn!("Ignoring the unreadable IP info cache {}: {}", path, err);
+                    Default::default()
+                })
+            }
+            Err(_) => Default::default(),
+        };
+
+        Self {
+            path: Some(path),
+            ttl,
+            entries,
+        }
+    }
+
+    pub fn from_options(options: &IpInfoOptions) -> Self {
+        match &options.ip_info_cache_path {
+            Some(path) => Self::load(path.clone(), options.ip_info_cache_ttl),
+            None => Default::default(),
+        }
+    }
+
+    fn get(&self, ip: &str, now: i64) -> Option<IpInfo> {
+        self.entries
+            .get(ip)
+            .filter(|cached| now - cached.fetched_at < self.ttl)
+            .map(|cached| cached.info.clone())
+    }
+
+    fn insert(&mut self, ip: String, info: IpInfo, now: i64) {
+        if self.path.is_some() {
+            self.entries.insert(
+                ip,
+                CachedIpInfo {
+                    fetched_at: now,
+                    info,
+                },
+            );
+        }
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        if let Some(path) = &self.path {
+            let file = std::fs::File::create(path)?;
+            serde_json::to_writer(std::io::BufWriter::new(file), &self.entries)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub async fn get_data_centers(
+    source: &IpInfoSource,
+    cache: &mut IpInfoCache,
     node_ips: HashMap<String, String>,
 ) -> anyhow::Result<HashMap<String, (String, IpInfo)>> {
     info!("Fetching info about data centers...");
+    let now = chrono::Utc::now().timestamp();
+
     let mut data_centers = HashMap::new();
+    let mut uncached_node_ips = vec![];
+    for (node, ip) in node_ips {
+        match cache.get(&ip, now) {
+            Some(info) => {
+                data_centers.insert(node, (ip, info));
+            }
+            None => uncached_node_ips.push((node, ip)),
+        }
+    }
+    info!(
+        "Info about {} IPs found in the cache, fetching {} IPs",
+        data_centers.len(),
+        uncached_node_ips.len()
+    );
 
-    for (node, ip) in node_ips.iter() {
-        match whois_client.get_ip_info(ip) {
-            Ok(info) => {
-                data_centers.insert(node.clone(), (ip.clone(), info));
+    let fetched: Vec<_> = stream::iter(uncached_node_ips)
+        .map(|(node, ip)| async move {
+            match source.get_ip_info(&ip).await {
+                Ok(info) => Some((node, (ip, info))),
+                Err(err) => {
+                    error!(
+                        "Error fetching info about IP {} of node {}: {}",
+                        ip, node, err
+                    );
+                    None
+                }
             }
-            Err(err) => error!(
-                "Error fetching info about IP {} of node {}: {}",
-                ip, node, err
-            ),
-        };
+        })
+        .buffer_unordered(WHOIS_CONCURRENCY)
+        .filter_map(|data_center| async move { data_center })
+        .collect()
+        .await;
+
+    for (node, (ip, info)) in fetched {
+        cache.insert(ip.clone(), info.clone(), now);
+        data_centers.insert(node, (ip, info));
+    }
+    if let Err(err) = cache.save() {
+        error!("Failed to save the IP info cache: {}", err);
     }
 
     info!("Fetched info about data centers...");
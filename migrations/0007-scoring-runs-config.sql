@@ -0,0 +1 @@
+alter table scoring_runs add column "config" text;
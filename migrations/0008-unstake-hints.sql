@@ -0,0 +1,8 @@
+CREATE TABLE "unstake_hints" (
+    "vote_account" text NOT NULL,
+    "epoch" numeric NOT NULL,
+    "hint" text NOT NULL,
+    "marinade_stake" double precision NOT NULL,
+    "created_at" timestamp with time zone NOT NULL,
+    PRIMARY KEY("vote_account", "epoch", "hint")
+);
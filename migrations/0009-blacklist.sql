@@ -0,0 +1,19 @@
+CREATE TABLE "blacklist" (
+    "vote_account" text NOT NULL,
+    "code" text NOT NULL,
+    "evidence" text,
+    "added_at" timestamp with time zone NOT NULL,
+    "expires_at" timestamp with time zone,
+    PRIMARY KEY("vote_account", "code")
+);
+CREATE TABLE "blacklist_audit" (
+    "blacklist_audit_id" bigserial NOT NULL,
+    "action" text NOT NULL,
+    "vote_account" text NOT NULL,
+    "code" text NOT NULL,
+    "evidence" text,
+    "expires_at" timestamp with time zone,
+    "comment" text,
+    "created_at" timestamp with time zone NOT NULL,
+    PRIMARY KEY("blacklist_audit_id")
+);
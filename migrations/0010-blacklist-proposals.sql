@@ -0,0 +1,12 @@
+CREATE TABLE "blacklist_proposals" (
+    "blacklist_proposal_id" bigserial NOT NULL,
+    "vote_account" text NOT NULL,
+    "code" text NOT NULL,
+    "epoch" numeric NOT NULL,
+    "evidence" text NOT NULL,
+    "status" text NOT NULL,
+    "created_at" timestamp with time zone NOT NULL,
+    "reviewed_at" timestamp with time zone,
+    PRIMARY KEY("blacklist_proposal_id"),
+    UNIQUE("vote_account", "code", "epoch")
+);
@@ -0,0 +1,11 @@
+CREATE TABLE "validator_events" (
+    "validator_event_id" bigserial NOT NULL,
+    "vote_account" text NOT NULL,
+    "event_type" text NOT NULL,
+    "epoch" numeric NOT NULL,
+    "value_before" text,
+    "value_after" text,
+    "created_at" timestamp with time zone NOT NULL,
+    PRIMARY KEY("validator_event_id")
+);
+CREATE INDEX validator_events_vote_account_created_at ON validator_events (vote_account, created_at);
@@ -0,0 +1,20 @@
+ALTER TABLE uptimes DROP CONSTRAINT uptimes_status_check;
+ALTER TABLE uptimes ADD CONSTRAINT uptimes_status_check CHECK (status IN ('UP', 'DOWN', 'UNKNOWN'));
+
+CREATE TABLE uptime_samples (
+  id BIGSERIAL NOT NULL,
+  vote_account TEXT NOT NULL,
+  epoch NUMERIC NOT NULL,
+  status TEXT CHECK (status IN ('UP', 'DOWN')) NOT NULL,
+  slot NUMERIC NOT NULL,
+  last_vote NUMERIC NOT NULL,
+  root_slot NUMERIC NOT NULL,
+  vote_lag NUMERIC NOT NULL,
+  root_lag NUMERIC NOT NULL,
+  credits NUMERIC NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+
+  PRIMARY KEY(id)
+);
+CREATE INDEX uptime_samples_vote_account_created_at ON uptime_samples (vote_account, created_at);
+CREATE INDEX uptime_samples_epoch_vote_account_created_at ON uptime_samples (epoch, vote_account, created_at);
@@ -0,0 +1,2 @@
+ALTER TABLE validators ADD COLUMN vote_latency DOUBLE PRECISION NULL;
+ALTER TABLE validators ADD COLUMN vote_credits_ratio DOUBLE PRECISION NULL;
@@ -0,0 +1,19 @@
+CREATE TABLE cluster_stats (
+  epoch NUMERIC NOT NULL,
+  total_activated_stake NUMERIC NOT NULL,
+  blocks_produced NUMERIC NOT NULL,
+  leader_slots NUMERIC NOT NULL,
+  avg_skip_rate DOUBLE PRECISION NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+
+  PRIMARY KEY(epoch)
+);
+
+CREATE TABLE cluster_dc_stakes (
+  epoch NUMERIC NOT NULL,
+  dimension TEXT CHECK (dimension IN ('ASO', 'ASN', 'CITY')) NOT NULL,
+  dc TEXT NOT NULL,
+  activated_stake NUMERIC NOT NULL,
+
+  PRIMARY KEY(epoch, dimension, dc)
+);
@@ -0,0 +1,12 @@
+CREATE TABLE cluster_decentralization (
+  epoch NUMERIC NOT NULL,
+  dimension TEXT CHECK (dimension IN ('VALIDATOR', 'ASO', 'COUNTRY', 'CITY')) NOT NULL,
+  nakamoto_coefficient INTEGER NOT NULL,
+  herfindahl_hirschman_index DOUBLE PRECISION NOT NULL,
+  gini_coefficient DOUBLE PRECISION NOT NULL,
+  nakamoto_coefficient_without_marinade INTEGER NOT NULL,
+  herfindahl_hirschman_index_without_marinade DOUBLE PRECISION NOT NULL,
+  gini_coefficient_without_marinade DOUBLE PRECISION NOT NULL,
+
+  PRIMARY KEY(epoch, dimension)
+);
@@ -0,0 +1,7 @@
+CREATE TABLE unclosable_epochs (
+  epoch NUMERIC NOT NULL,
+  error TEXT NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+
+  PRIMARY KEY(epoch)
+);
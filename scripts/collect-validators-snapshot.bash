@@ -5,10 +5,24 @@ set -e
 SCRIPT_DIR=$(dirname "$0")
 BIN_DIR="${BIN_DIR:-"$SCRIPT_DIR/../target/debug"}"
 
-if [[ -z $WHOIS_BEARER_TOKEN ]]
+if [[ -n $GEOIP_CITY_DB ]]
+then
+  IP_INFO_ARGS=(--geoip-city-db "$GEOIP_CITY_DB")
+  if [[ -n $GEOIP_ASN_DB ]]
+  then
+    IP_INFO_ARGS+=(--geoip-asn-db "$GEOIP_ASN_DB")
+  fi
+elif [[ -z $WHOIS_BEARER_TOKEN ]]
 then
   echo "Env variable WHOIS_BEARER_TOKEN is missing!" >&2
   exit 1
+else
+  IP_INFO_ARGS=(--whois "https://whois.marinade.finance" --whois-bearer-token "$WHOIS_BEARER_TOKEN")
+fi
+
+if [[ -n $IP_INFO_CACHE_FILE ]]
+then
+  IP_INFO_ARGS+=(--ip-info-cache-file "$IP_INFO_CACHE_FILE")
 fi
 
 if [[ -z $RPC_URL ]]
@@ -17,10 +31,16 @@ then
   exit 1
 fi
 
+MARINADE_ARGS=()
+if [[ -n $MARINADE_CONFIG ]]
+then
+  MARINADE_ARGS=(--marinade-config "$MARINADE_CONFIG")
+fi
+
 "$BIN_DIR/collect" \
   --url "$RPC_URL" \
   validators \
-    --whois "https://whois.marinade.finance" \
-    --whois-bearer-token "$WHOIS_BEARER_TOKEN" \
+    "${IP_INFO_ARGS[@]}" \
     --escrow-relocker "tovt1VkTE2T4caWoeFP6a2xSFoew5mNpd7FWidyyMuk" \
-    --gauge-meister "mvgmBamY7hDWxLNGLshMoZn8nt2P8tKnKhaBeXMVajZ"
+    --gauge-meister "mvgmBamY7hDWxLNGLshMoZn8nt2P8tKnKhaBeXMVajZ" \
+    "${MARINADE_ARGS[@]}"
@@ -0,0 +1,56 @@
+#!/bin/bash
+
+set -e
+
+SCRIPT_DIR=$(dirname "$0")
+BIN_DIR="${BIN_DIR:-"$SCRIPT_DIR/../target/debug"}"
+
+if [[ -z $POSTGRES_URL ]]
+then
+  echo "Env variable POSTGRES_URL is missing!" >&2
+  exit 1
+fi
+
+if [[ -z $RPC_URL ]]
+then
+  echo "Env variable RPC_URL is missing!" >&2
+  exit 1
+fi
+
+IP_INFO_ARGS=(--whois "https://whois.marinade.finance" --whois-bearer-token "$WHOIS_BEARER_TOKEN")
+if [[ -n $GEOIP_CITY_DB ]]
+then
+  IP_INFO_ARGS=(--geoip-city-db "$GEOIP_CITY_DB")
+  if [[ -n $GEOIP_ASN_DB ]]
+  then
+    IP_INFO_ARGS+=(--geoip-asn-db "$GEOIP_ASN_DB")
+  fi
+fi
+
+if [[ -n $IP_INFO_CACHE_FILE ]]
+then
+  IP_INFO_ARGS+=(--ip-info-cache-file "$IP_INFO_CACHE_FILE")
+fi
+
+NOTIFICATIONS_ARGS=()
+if [[ -n $NOTIFICATIONS_CONFIG ]]
+then
+  NOTIFICATIONS_ARGS=(--notifications-config "$NOTIFICATIONS_CONFIG")
+fi
+
+MARINADE_ARGS=()
+if [[ -n $MARINADE_CONFIG ]]
+then
+  MARINADE_ARGS=(--marinade-config "$MARINADE_CONFIG")
+fi
+
+"$BIN_DIR/store" \
+  --postgres-url "$POSTGRES_URL" \
+  daemon \
+    --url "$RPC_URL" \
+    "${IP_INFO_ARGS[@]}" \
+    --escrow-relocker "tovt1VkTE2T4caWoeFP6a2xSFoew5mNpd7FWidyyMuk" \
+    --gauge-meister "mvgmBamY7hDWxLNGLshMoZn8nt2P8tKnKhaBeXMVajZ" \
+    "${MARINADE_ARGS[@]}" \
+    "${NOTIFICATIONS_ARGS[@]}" \
+    "$@"
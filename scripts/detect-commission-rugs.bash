@@ -0,0 +1,33 @@
+#!/bin/bash
+
+set -e
+
+SCRIPT_DIR=$(dirname "$0")
+BIN_DIR="${BIN_DIR:-"$SCRIPT_DIR/../target/debug"}"
+
+if [[ -z $POSTGRES_URL ]]
+then
+  echo "Env variable POSTGRES_URL is missing!" >&2
+  exit 1
+fi
+
+if [[ -z $RPC_URL ]]
+then
+  echo "Env variable RPC_URL is missing!" >&2
+  exit 1
+fi
+
+EPOCH="$1"
+if [[ -z $EPOCH ]]
+then
+  echo "Usage: $0 <closed-epoch> [additional options]" >&2
+  exit 1
+fi
+shift 1
+
+"$BIN_DIR/store" \
+  --postgres-url "$POSTGRES_URL" \
+  detect-commission-rugs \
+    --url "$RPC_URL" \
+    --epoch "$EPOCH" \
+    "$@"
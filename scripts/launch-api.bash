@@ -5,7 +5,8 @@ set -e
 SCRIPT_DIR=$(dirname "$0")
 BIN_DIR="${BIN_DIR:-"$SCRIPT_DIR/../target/debug"}"
 GLOSSARY_MD="${GLOSSARY_MD:-"$SCRIPT_DIR/../glossary.md"}"
-BLACKLIST_CSV="${BLACKLIST_CSV:-"$SCRIPT_DIR/../blacklist.csv"}"
+UNSTAKE_HINTS_CONFIG="${UNSTAKE_HINTS_CONFIG:-"$SCRIPT_DIR/../unstake-hints-config.yaml"}"
+MSOL_VOTES_CSV="${MSOL_VOTES_CSV:-"$SCRIPT_DIR/../msol-votes.csv"}"
 
 if [[ -z $POSTGRES_URL ]]
 then
@@ -16,4 +17,5 @@ fi
 "$BIN_DIR/api" \
   --postgres-url "$POSTGRES_URL" \
   --glossary-path "$GLOSSARY_MD" \
-  --blacklist-path "$BLACKLIST_CSV"
+  --unstake-hints-config-path "$UNSTAKE_HINTS_CONFIG" \
+  --msol-votes-path "$MSOL_VOTES_CSV"
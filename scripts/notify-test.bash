@@ -0,0 +1,28 @@
+#!/bin/bash
+
+set -e
+
+SCRIPT_DIR=$(dirname "$0")
+BIN_DIR="${BIN_DIR:-"$SCRIPT_DIR/../target/debug"}"
+
+if [[ -z $POSTGRES_URL ]]
+then
+  echo "Env variable POSTGRES_URL is missing!"
+  exit 1
+fi
+
+NOTIFICATIONS_CONFIG="$1"
+VOTE_ACCOUNT="$2"
+EVENT_TYPE="${3:-CommissionChange}"
+if [[ -z $NOTIFICATIONS_CONFIG ]] || [[ -z $VOTE_ACCOUNT ]]
+then
+  echo "Usage: $0 <notifications-config> <vote-account> [event-type]" >&2
+  exit 1
+fi
+
+"$BIN_DIR/store" \
+  --postgres-url "$POSTGRES_URL" \
+  notify-test \
+    --notifications-config "$NOTIFICATIONS_CONFIG" \
+    --vote-account "$VOTE_ACCOUNT" \
+    --event-type "$EVENT_TYPE"
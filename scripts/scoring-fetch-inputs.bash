@@ -9,6 +9,7 @@ file_parsed_msol_votes="./msol-votes.csv"
 file_validators="./validators.csv"
 file_blacklist="./blacklist.csv"
 file_params="./params.env"
+file_config="./scoring-config.yaml"
 file_unstake_hints="./unstake-hints.json"
 
 current_epoch=$(curl -sfLS http://api.mainnet-beta.solana.com -X POST -H "Content-Type: application/json" -d '
@@ -62,3 +63,32 @@ STAKE_CONTROL_MSOL=0.2
 EOF
 
 cat "$file_params"
+
+cat <<EOF > "$file_config"
+version: 1
+total_stake: $TOTAL_STAKE
+marinade_validators_count: 100
+weights:
+  adjusted_credits: 10
+  grace_skip_rate: 1
+  dc_concentration: 2
+eligibility:
+  min_version: 1.13.5
+  algo_stake:
+    max_commission: 10
+    min_stake: 1000
+  mnde_stake:
+    max_commission: 10
+    min_stake: 100
+    score_threshold_multiplier: 0.9
+  msol_stake:
+    max_commission: 10
+    min_stake: 100
+    score_threshold_multiplier: 0.8
+stake_control:
+  mnde: 0.2
+  msol: 0.2
+mnde_validator_cap: 0.1
+EOF
+
+cat "$file_config"
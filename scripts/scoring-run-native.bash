@@ -0,0 +1,32 @@
+#!/bin/bash
+
+set -e
+
+SCRIPT_DIR=$(dirname "$0")
+BIN_DIR="${BIN_DIR:-"$SCRIPT_DIR/../target/debug"}"
+WORKING_DIRECTORY=${SCORING_WORKING_DIRECTORY:-"$SCRIPT_DIR/.."}
+
+if [[ -z $POSTGRES_URL ]]
+then
+  echo "Env variable POSTGRES_URL is missing!" >&2
+  exit 1
+fi
+
+EPOCH="$1"
+UI_ID="$2"
+if [[ -z $EPOCH ]] || [[ -z $UI_ID ]]
+then
+  echo "Usage: $0 <last-epoch> <ui-id> [additional options]" >&2
+  exit 1
+fi
+shift 2
+
+"$BIN_DIR/store" \
+  --postgres-url "$POSTGRES_URL" \
+  scoring-run \
+    --epoch "$EPOCH" \
+    --ui-id "$UI_ID" \
+    --config-file "$WORKING_DIRECTORY/scoring-config.yaml" \
+    --msol-votes-file "$WORKING_DIRECTORY/msol-votes.csv" \
+    --output-csv "$WORKING_DIRECTORY/scores-native.csv" \
+    "$@"
@@ -0,0 +1,19 @@
+#!/bin/bash
+
+set -e
+
+SCRIPT_DIR=$(dirname "$0")
+BIN_DIR="${BIN_DIR:-"$SCRIPT_DIR/../target/debug"}"
+
+if [[ -z $POSTGRES_URL ]]
+then
+  echo "Env variable POSTGRES_URL is missing!" >&2
+  exit 1
+fi
+
+BLACKLIST="${1:-"$SCRIPT_DIR/../blacklist.csv"}"
+
+"$BIN_DIR/store" \
+  --postgres-url "$POSTGRES_URL" \
+  blacklist-import \
+    --blacklist-path "$BLACKLIST"
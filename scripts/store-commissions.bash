@@ -18,7 +18,14 @@ then
   exit 1
 fi
 
+NOTIFICATIONS_ARGS=()
+if [[ -n $NOTIFICATIONS_CONFIG ]]
+then
+  NOTIFICATIONS_ARGS=(--notifications-config "$NOTIFICATIONS_CONFIG")
+fi
+
 "$BIN_DIR/store" \
   --postgres-url "$POSTGRES_URL" \
   commissions \
-    --snapshot-file "$SNAPSHOT"
+    --snapshot-file "$SNAPSHOT" \
+    "${NOTIFICATIONS_ARGS[@]}"
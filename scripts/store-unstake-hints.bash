@@ -0,0 +1,26 @@
+#!/bin/bash
+
+set -e
+
+SCRIPT_DIR=$(dirname "$0")
+BIN_DIR="${BIN_DIR:-"$SCRIPT_DIR/../target/debug"}"
+
+if [[ -z $POSTGRES_URL ]]
+then
+  echo "Env variable POSTGRES_URL is missing!" >&2
+  exit 1
+fi
+
+EPOCH="$1"
+UNSTAKE_HINTS_CONFIG="${UNSTAKE_HINTS_CONFIG:-"$SCRIPT_DIR/../unstake-hints-config.yaml"}"
+if [[ -z $EPOCH ]]
+then
+  echo "Usage: $0 <epoch>" >&2
+  exit 1
+fi
+
+"$BIN_DIR/store" \
+  --postgres-url "$POSTGRES_URL" \
+  unstake-hints \
+    --epoch "$EPOCH" \
+    --config-file "$UNSTAKE_HINTS_CONFIG"
@@ -0,0 +1,45 @@
+use crate::utils::{compute_cluster_stats, store_cluster_stats};
+use log::info;
+use rust_decimal::prelude::*;
+use structopt::StructOpt;
+use tokio_postgres::Client;
+
+#[derive(Debug, StructOpt)]
+pub struct BackfillClusterStatsOptions {}
+
+/// Epochs closed before the cluster stats were stored by `close-epoch`
+async fn load_closed_epochs_without_cluster_stats(
+    psql_client: &Client,
+) -> anyhow::Result<Vec<u64>> {
+    psql_client
+        .query(
+            "
+        SELECT epoch
+        FROM epochs
+        WHERE epoch NOT IN (SELECT epoch FROM cluster_stats)
+        ORDER BY epoch
+    ",
+            &[],
+        )
+        .await?
+        .iter()
+        .map(|row| Ok(row.get::<_, Decimal>("epoch").try_into()?))
+        .collect()
+}
+
+pub async fn backfill_cluster_stats(psql_client: &mut Client) -> anyhow::Result<()> {
+    info!("Backfilling cluster stats...");
+
+    let epochs = load_closed_epochs_without_cluster_stats(psql_client).await?;
+    info!("Epochs without cluster stats: {:?}", epochs);
+
+    for epoch in epochs.iter() {
+        let cluster_stats = compute_cluster_stats(psql_client, *epoch).await?;
+        store_cluster_stats(psql_client, &cluster_stats).await?;
+        info!("Stored cluster stats of epoch {}", epoch);
+    }
+
+    info!("Backfilled cluster stats: {}", epochs.len());
+
+    Ok(())
+}
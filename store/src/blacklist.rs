@@ -0,0 +1,390 @@
+use crate::dto::{BlacklistAuditRecord, BlacklistEntry, BlacklistProposalRecord, BlacklistRecord};
+use rust_decimal::prelude::*;
+use std::collections::{HashMap, HashSet};
+use tokio_postgres::{Client, Row, Transaction};
+
+pub const BLACKLIST_ACTION_ADD: &str = "ADD";
+pub const BLACKLIST_ACTION_REMOVE: &str = "REMOVE";
+pub const BLACKLIST_ACTION_IMPORT: &str = "IMPORT";
+pub const BLACKLIST_ACTION_PROPOSAL_ACCEPTED: &str = "PROPOSAL_ACCEPTED";
+
+pub const BLACKLIST_CODE_COMMISSION_RUG: &str = "BLACKLIST_COMMISSION_RUG";
+
+pub const PROPOSAL_STATUS_PENDING: &str = "PENDING";
+pub const PROPOSAL_STATUS_ACCEPTED: &str = "ACCEPTED";
+pub const PROPOSAL_STATUS_REJECTED: &str = "REJECTED";
+
+const BLACKLIST_COLUMNS: &str = "vote_account, code, evidence, added_at, expires_at";
+const BLACKLIST_PROPOSAL_COLUMNS: &str =
+    "blacklist_proposal_id, vote_account, code, epoch, evidence, status, created_at, reviewed_at";
+
+/// Reason codes end up in the UI hints, e.g. `BLACKLIST_COMMISSION_RUG` or `SLOW_VOTER`
+pub fn validate_blacklist_code(code: &str) -> anyhow::Result<()> {
+    if code.is_empty()
+        || !code
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    {
+        anyhow::bail!("Invalid blacklist code: '{}'", code);
+    }
+
+    Ok(())
+}
+
+pub fn load_blacklist_csv(blacklist_path: &String) -> anyhow::Result<Vec<BlacklistRecord>> {
+    let mut blacklist: Vec<BlacklistRecord> = Default::default();
+    let mut rdr = csv::Reader::from_path(blacklist_path)?;
+    for result in rdr.deserialize() {
+        blacklist.push(result?);
+    }
+
+    Ok(blacklist)
+}
+
+pub fn group_codes_by_vote_account(
+    entries: impl IntoIterator<Item = (String, String)>,
+) -> HashMap<String, HashSet<String>> {
+    entries
+        .into_iter()
+        .fold(HashMap::new(), |mut acc, (vote_account, code)| {
+            acc.entry(vote_account).or_default().insert(code);
+
+            acc
+        })
+}
+
+fn blacklist_entry_from_row(row: &Row) -> BlacklistEntry {
+    BlacklistEntry {
+        vote_account: row.get("vote_account"),
+        code: row.get("code"),
+        evidence: row.get("evidence"),
+        added_at: row.get("added_at"),
+        expires_at: row.get("expires_at"),
+    }
+}
+
+pub async fn load_blacklist_entries(
+    psql_client: &Client,
+    include_expired: bool,
+) -> anyhow::Result<Vec<BlacklistEntry>> {
+    Ok(psql_client
+        .query(
+            &format!(
+                "SELECT {}
+                FROM blacklist
+                WHERE $1 OR expires_at IS NULL OR expires_at > now()
+                ORDER BY vote_account, code",
+                BLACKLIST_COLUMNS
+            ),
+            &[&include_expired],
+        )
+        .await?
+        .iter()
+        .map(blacklist_entry_from_row)
+        .collect())
+}
+
+/// Blacklist codes of validators with entries which have not expired yet
+pub async fn load_active_blacklist(
+    psql_client: &Client,
+) -> anyhow::Result<HashMap<String, HashSet<String>>> {
+    log::info!("Loading the blacklist");
+    let entries = load_blacklist_entries(psql_client, false).await?;
+
+    Ok(group_codes_by_vote_account(
+        entries
+            .into_iter()
+            .map(|entry| (entry.vote_account, entry.code)),
+    ))
+}
+
+async fn store_audit_record(
+    transaction: &Transaction<'_>,
+    action: &str,
+    entry: &BlacklistEntry,
+    comment: &Option<String>,
+) -> anyhow::Result<()> {
+    transaction
+        .execute(
+            "INSERT INTO blacklist_audit (action, vote_account, code, evidence, expires_at, comment, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, now())",
+            &[
+                &action,
+                &entry.vote_account,
+                &entry.code,
+                &entry.evidence,
+                &entry.expires_at,
+                comment,
+            ],
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn upsert_blacklist_entry(
+    transaction: &Transaction<'_>,
+    record: &BlacklistRecord,
+    action: &str,
+    comment: &Option<String>,
+) -> anyhow::Result<BlacklistEntry> {
+    let row = transaction
+        .query_one(
+            &format!(
+                "INSERT INTO blacklist ({})
+                VALUES ($1, $2, $3, now(), $4)
+                ON CONFLICT (vote_account, code) DO UPDATE
+                SET evidence = EXCLUDED.evidence, expires_at = EXCLUDED.expires_at
+                RETURNING {}",
+                BLACKLIST_COLUMNS, BLACKLIST_COLUMNS
+            ),
+            &[
+                &record.vote_account,
+                &record.code,
+                &record.evidence,
+                &record.expires_at,
+            ],
+        )
+        .await?;
+    let entry = blacklist_entry_from_row(&row);
+    store_audit_record(transaction, action, &entry, comment).await?;
+
+    log::info!("Blacklisted {} ({})", entry.vote_account, entry.code);
+
+    Ok(entry)
+}
+
+/// Adds the entry to the blacklist, evidence and expiration of an existing entry are overwritten
+pub async fn add_blacklist_entry(
+    psql_client: &mut Client,
+    record: &BlacklistRecord,
+    comment: Option<String>,
+) -> anyhow::Result<BlacklistEntry> {
+    validate_blacklist_code(&record.code)?;
+
+    let transaction = psql_client.transaction().await?;
+    let entry =
+        upsert_blacklist_entry(&transaction, record, BLACKLIST_ACTION_ADD, &comment).await?;
+    transaction.commit().await?;
+
+    Ok(entry)
+}
+
+/// Removes all entries of the validator, or only the one with the given code
+pub async fn remove_blacklist_entries(
+    psql_client: &mut Client,
+    vote_account: &String,
+    code: &Option<String>,
+    comment: Option<String>,
+) -> anyhow::Result<Vec<BlacklistEntry>> {
+    let transaction = psql_client.transaction().await?;
+    let entries: Vec<_> = transaction
+        .query(
+            &format!(
+                "DELETE FROM blacklist
+                WHERE vote_account = $1 AND ($2::text IS NULL OR code = $2)
+                RETURNING {}",
+                BLACKLIST_COLUMNS
+            ),
+            &[vote_account, code],
+        )
+        .await?
+        .iter()
+        .map(blacklist_entry_from_row)
+        .collect();
+    for entry in entries.iter() {
+        store_audit_record(&transaction, BLACKLIST_ACTION_REMOVE, entry, &comment).await?;
+    }
+    transaction.commit().await?;
+
+    log::info!(
+        "Removed {} blacklist entries of {}",
+        entries.len(),
+        vote_account
+    );
+
+    Ok(entries)
+}
+
+/// Imports records from `blacklist.csv`, entries already in the blacklist are left untouched
+pub async fn import_blacklist(
+    psql_client: &mut Client,
+    records: &[BlacklistRecord],
+    comment: Option<String>,
+) -> anyhow::Result<u64> {
+    for record in records {
+        validate_blacklist_code(&record.code)?;
+    }
+
+    let transaction = psql_client.transaction().await?;
+    let mut imported = 0;
+    for record in records {
+        let row = transaction
+            .query_opt(
+                &format!(
+                    "INSERT INTO blacklist ({})
+                    VALUES ($1, $2, $3, now(), $4)
+                    ON CONFLICT (vote_account, code) DO NOTHING
+                    RETURNING {}",
+                    BLACKLIST_COLUMNS, BLACKLIST_COLUMNS
+                ),
+                &[
+                    &record.vote_account,
+                    &record.code,
+                    &record.evidence,
+                    &record.expires_at,
+                ],
+            )
+            .await?;
+        if let Some(row) = row {
+            let entry = blacklist_entry_from_row(&row);
+            store_audit_record(&transaction, BLACKLIST_ACTION_IMPORT, &entry, &comment).await?;
+            imported += 1;
+        }
+    }
+    transaction.commit().await?;
+
+    Ok(imported)
+}
+
+pub async fn load_blacklist_audit(
+    psql_client: &Client,
+    vote_account: Option<String>,
+) -> anyhow::Result<Vec<BlacklistAuditRecord>> {
+    Ok(psql_client
+        .query(
+            "SELECT blacklist_audit_id, action, vote_account, code, evidence, expires_at, comment, created_at
+            FROM blacklist_audit
+            WHERE $1::text IS NULL OR vote_account = $1
+            ORDER BY blacklist_audit_id DESC",
+            &[&vote_account],
+        )
+        .await?
+        .iter()
+        .map(|row| BlacklistAuditRecord {
+            blacklist_audit_id: row.get("blacklist_audit_id"),
+            action: row.get("action"),
+            vote_account: row.get("vote_account"),
+            code: row.get("code"),
+            evidence: row.get("evidence"),
+            expires_at: row.get("expires_at"),
+            comment: row.get("comment"),
+            created_at: row.get("created_at"),
+        })
+        .collect())
+}
+
+fn blacklist_proposal_from_row(row: &Row) -> anyhow::Result<BlacklistProposalRecord> {
+    Ok(BlacklistProposalRecord {
+        blacklist_proposal_id: row.get("blacklist_proposal_id"),
+        vote_account: row.get("vote_account"),
+        code: row.get("code"),
+        epoch: row.get::<_, Decimal>("epoch").try_into()?,
+        evidence: row.get("evidence"),
+        status: row.get("status"),
+        created_at: row.get("created_at"),
+        reviewed_at: row.get("reviewed_at"),
+    })
+}
+
+/// Stores new pending proposals, a proposal for the same validator, code and epoch is stored only once
+pub async fn store_blacklist_proposals(
+    psql_client: &mut Client,
+    epoch: u64,
+    proposals: &[BlacklistRecord],
+) -> anyhow::Result<u64> {
+    let epoch = Decimal::from(epoch);
+    let transaction = psql_client.transaction().await?;
+    let mut stored = 0;
+    for proposal in proposals {
+        stored += transaction
+            .execute(
+                "INSERT INTO blacklist_proposals (vote_account, code, epoch, evidence, status, created_at)
+                VALUES ($1, $2, $3, $4, $5, now())
+                ON CONFLICT (vote_account, code, epoch) DO NOTHING",
+                &[
+                    &proposal.vote_account,
+                    &proposal.code,
+                    &epoch,
+                    &proposal.evidence,
+                    &PROPOSAL_STATUS_PENDING,
+                ],
+            )
+            .await?;
+    }
+    transaction.commit().await?;
+
+    Ok(stored)
+}
+
+pub async fn load_blacklist_proposals(
+    psql_client: &Client,
+    status: Option<String>,
+) -> anyhow::Result<Vec<BlacklistProposalRecord>> {
+    psql_client
+        .query(
+            &format!(
+                "SELECT {}
+                FROM blacklist_proposals
+                WHERE $1::text IS NULL OR status = $1
+                ORDER BY blacklist_proposal_id DESC",
+                BLACKLIST_PROPOSAL_COLUMNS
+            ),
+            &[&status],
+        )
+        .await?
+        .iter()
+        .map(blacklist_proposal_from_row)
+        .collect()
+}
+
+/// Accepted proposals are added to the blacklist, returns `None` if there is no pending proposal with the ID
+pub async fn review_blacklist_proposal(
+    psql_client: &mut Client,
+    blacklist_proposal_id: i64,
+    accept: bool,
+    comment: Option<String>,
+) -> anyhow::Result<Option<BlacklistProposalRecord>> {
+    let status = if accept {
+        PROPOSAL_STATUS_ACCEPTED
+    } else {
+        PROPOSAL_STATUS_REJECTED
+    };
+
+    let transaction = psql_client.transaction().await?;
+    let row = transaction
+        .query_opt(
+            &format!(
+                "UPDATE blacklist_proposals
+                SET status = $2, reviewed_at = now()
+                WHERE blacklist_proposal_id = $1 AND status = $3
+                RETURNING {}",
+                BLACKLIST_PROPOSAL_COLUMNS
+            ),
+            &[&blacklist_proposal_id, &status, &PROPOSAL_STATUS_PENDING],
+        )
+        .await?;
+    let proposal = match row {
+        Some(row) => blacklist_proposal_from_row(&row)?,
+        None => return Ok(None),
+    };
+
+    if accept {
+        let record = BlacklistRecord {
+            vote_account: proposal.vote_account.clone(),
+            code: proposal.code.clone(),
+            evidence: Some(proposal.evidence.clone()),
+            expires_at: None,
+        };
+        upsert_blacklist_entry(
+            &transaction,
+            &record,
+            BLACKLIST_ACTION_PROPOSAL_ACCEPTED,
+            &comment,
+        )
+        .await?;
+    }
+    transaction.commit().await?;
+
+    Ok(Some(proposal))
+}
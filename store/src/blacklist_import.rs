@@ -0,0 +1,35 @@
+use log::info;
+use store::blacklist::{import_blacklist, load_blacklist_csv};
+use structopt::StructOpt;
+use tokio_postgres::Client;
+
+#[derive(Debug, StructOpt)]
+pub struct BlacklistImportOptions {
+    #[structopt(long = "blacklist-path")]
+    blacklist_path: String,
+
+    #[structopt(
+        long = "comment",
+        help = "Stored in the audit trail of the imported entries"
+    )]
+    comment: Option<String>,
+}
+
+pub async fn blacklist_import(
+    options: BlacklistImportOptions,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    info!("Importing the blacklist...");
+
+    let records = load_blacklist_csv(&options.blacklist_path)?;
+    info!("Loaded {} blacklist records", records.len());
+
+    let imported = import_blacklist(psql_client, &records, options.comment).await?;
+    info!(
+        "Imported {} new blacklist entries, {} were already present",
+        imported,
+        records.len() as u64 - imported
+    );
+
+    Ok(())
+}
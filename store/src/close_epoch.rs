@@ -1,4 +1,4 @@
-use crate::utils::UpdateQueryCombiner;
+use crate::utils::{compute_cluster_stats, store_cluster_stats, UpdateQueryCombiner};
 use chrono::{DateTime, Utc};
 use collect::validators_performance::{ClusterInflation, ValidatorsPerformanceSnapshot};
 use log::info;
@@ -101,22 +101,29 @@ pub async fn update_observed_commission(psql_client: &Client, epoch: u64) -> any
     Ok(())
 }
 
+/// Time with unknown status (collection gaps) is left out, the uptime is NULL when no status is known
 pub async fn update_uptimes(psql_client: &Client, epoch: u64) -> anyhow::Result<()> {
     psql_client
             .execute("
                 WITH uptimes AS (
                     WITH
                         vars AS (SELECT epoch, end_at - start_at AS epoch_duration FROM epochs WHERE epoch = $1),
-                        downtimes AS (SELECT vote_account, SUM(end_at - start_at) AS downtime FROM uptimes WHERE epoch = $1 AND status = 'DOWN' GROUP BY vote_account)
+                        downtimes AS (SELECT vote_account, SUM(end_at - start_at) AS downtime FROM uptimes WHERE epoch = $1 AND status = 'DOWN' GROUP BY vote_account),
+                        unknowns AS (SELECT vote_account, SUM(end_at - start_at) AS unknown FROM uptimes WHERE epoch = $1 AND status = 'UNKNOWN' GROUP BY vote_account),
+                        known AS (
+                            SELECT validators.vote_account, GREATEST(vars.epoch_duration - COALESCE(unknowns.unknown, '0 seconds'), '0 seconds') AS known_duration
+                            FROM validators INNER JOIN vars ON validators.epoch = vars.epoch LEFT JOIN unknowns ON validators.vote_account = unknowns.vote_account
+                        )
                     SELECT
-                        LEAST(GREATEST(COALESCE(1 - EXTRACT('epoch' FROM downtimes.downtime) / EXTRACT('epoch' FROM vars.epoch_duration), 1), 0), 1) uptime_pct,
-                        EXTRACT('epoch' FROM GREATEST(COALESCE(vars.epoch_duration - downtimes.downtime, vars.epoch_duration), '0 seconds')) uptime,
+                        CASE WHEN known.known_duration > '0 seconds' THEN LEAST(GREATEST(COALESCE(1 - EXTRACT('epoch' FROM downtimes.downtime) / EXTRACT('epoch' FROM known.known_duration), 1), 0), 1) END uptime_pct,
+                        EXTRACT('epoch' FROM GREATEST(COALESCE(known.known_duration - downtimes.downtime, known.known_duration), '0 seconds')) uptime,
                         EXTRACT('epoch' FROM COALESCE(downtimes.downtime, '0 seconds')) downtime,
                         validators.vote_account,
                         vars.epoch
                     FROM
                         validators
                         INNER JOIN vars ON validators.epoch = vars.epoch
+                        INNER JOIN known ON validators.vote_account = known.vote_account
                         LEFT JOIN downtimes ON validators.vote_account = downtimes.vote_account
                     WHERE validators.epoch = $1
                 )
@@ -135,6 +142,44 @@ pub async fn update_uptimes(psql_client: &Client, epoch: u64) -> anyhow::Result<
     Ok(())
 }
 
+/// Vote latency is the average distance of the last vote from the current slot in the uptime samples,
+/// vote credits ratio compares the earned credits (i.e. credits per slot) with the best validator in the epoch
+pub async fn update_vote_latencies(psql_client: &Client, epoch: u64) -> anyhow::Result<()> {
+    psql_client
+            .execute("
+                WITH
+                    latencies AS (SELECT vote_account, AVG(vote_lag)::DOUBLE PRECISION AS vote_latency FROM uptime_samples WHERE epoch = $1 AND slot > 0 GROUP BY vote_account),
+                    cluster AS (SELECT MAX(credits) AS max_credits FROM validators WHERE epoch = $1)
+                UPDATE validators
+                SET
+                    vote_latency = (SELECT latencies.vote_latency FROM latencies WHERE latencies.vote_account = validators.vote_account),
+                    vote_credits_ratio = CASE WHEN cluster.max_credits > 0 THEN (validators.credits / cluster.max_credits)::DOUBLE PRECISION END
+                FROM cluster
+                WHERE validators.epoch = $1
+                "
+,
+        &[
+            &Decimal::from(epoch),
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Samples are needed only until the epoch is closed, their aggregates are kept in `validators`
+pub async fn prune_uptime_samples(psql_client: &Client, epoch: u64) -> anyhow::Result<()> {
+    let pruned = psql_client
+        .execute(
+            "DELETE FROM uptime_samples WHERE epoch = $1",
+            &[&Decimal::from(epoch)],
+        )
+        .await?;
+    info!("Pruned uptime samples of epoch {}: {}", epoch, pruned);
+
+    Ok(())
+}
+
 struct ValidatorUpdateRecord {
     vote_account: String,
     epoch: Decimal,
@@ -148,12 +193,20 @@ struct ValidatorUpdateRecord {
 
 pub async fn close_epoch(
     options: CloseEpochOptions,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
+    let snapshot: ValidatorsPerformanceSnapshot = serde_yaml::from_reader(snapshot_file)?;
+
+    close_epoch_snapshot(snapshot, psql_client).await
+}
+
+pub async fn close_epoch_snapshot(
+    snapshot: ValidatorsPerformanceSnapshot,
     mut psql_client: &mut Client,
 ) -> anyhow::Result<()> {
     info!("Finalizing validators snapshot...");
 
-    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
-    let snapshot: ValidatorsPerformanceSnapshot = serde_yaml::from_reader(snapshot_file)?;
     let snapshot_created_at = snapshot.created_at.parse::<DateTime<Utc>>().unwrap();
     let snapshot_epoch: Decimal = snapshot.epoch.into();
     let rewards = snapshot.rewards.unwrap();
@@ -245,6 +298,12 @@ pub async fn close_epoch(
 
     update_uptimes(&mut psql_client, snapshot.epoch).await?;
     update_observed_commission(&mut psql_client, snapshot.epoch).await?;
+    update_vote_latencies(psql_client, snapshot.epoch).await?;
+
+    let cluster_stats = compute_cluster_stats(psql_client, snapshot.epoch).await?;
+    store_cluster_stats(psql_client, &cluster_stats).await?;
+
+    prune_uptime_samples(psql_client, snapshot.epoch).await?;
 
     Ok(())
 }
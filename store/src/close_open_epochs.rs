@@ -0,0 +1,128 @@
+use crate::close_epoch::close_epoch_snapshot;
+use crate::ls_open_epochs::load_open_epochs;
+use chrono::Utc;
+use collect::common::CommonParams as RpcParams;
+use collect::rpc_service::{rpc_client, write_rpc_session};
+use collect::solana_service::SlotHistoryRangeError;
+use collect::validators_performance::{
+    validators_performance_snapshot, ValidatorsPerformanceOptions,
+};
+use log::{error, info, warn};
+use rust_decimal::prelude::*;
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use structopt::StructOpt;
+use tokio_postgres::Client;
+
+#[derive(Debug, StructOpt)]
+pub struct CloseOpenEpochsOptions {
+    #[structopt(flatten)]
+    rpc: RpcParams,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EpochError {
+    pub epoch: u64,
+    pub error: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct CloseOpenEpochsReport {
+    pub closed_epochs: Vec<u64>,
+    /// Block production of these epochs is no longer in the SlotHistory sysvar,
+    /// they are stored in `unclosable_epochs` and not listed as open anymore
+    pub unclosable_epochs: Vec<EpochError>,
+    pub failed_epochs: Vec<EpochError>,
+}
+
+async fn close_open_epoch(
+    rpc_client: &RpcClient,
+    epoch: u64,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    let snapshot = validators_performance_snapshot(
+        rpc_client,
+        &ValidatorsPerformanceOptions {
+            with_rewards: true,
+            epoch: Some(epoch),
+        },
+    )
+    .await?;
+
+    close_epoch_snapshot(snapshot, psql_client).await
+}
+
+async fn store_unclosable_epoch(
+    psql_client: &Client,
+    epoch: u64,
+    error: &String,
+) -> anyhow::Result<()> {
+    psql_client
+        .execute(
+            "INSERT INTO unclosable_epochs (epoch, error, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (epoch) DO NOTHING",
+            &[&Decimal::from(epoch), error, &Utc::now()],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Closes the open epochs that already ended, the rewards are known only after the epoch end
+pub async fn close_open_epochs(
+    rpc_client: &RpcClient,
+    psql_client: &mut Client,
+) -> anyhow::Result<CloseOpenEpochsReport> {
+    let current_epoch = rpc_client.get_epoch_info().await?.epoch;
+    let open_epochs: Vec<_> = load_open_epochs(psql_client)
+        .await?
+        .into_iter()
+        .filter(|epoch| *epoch < current_epoch)
+        .collect();
+    info!("Epochs to close: {:?}", open_epochs);
+
+    let mut report: CloseOpenEpochsReport = Default::default();
+    for epoch in open_epochs {
+        match close_open_epoch(rpc_client, epoch, psql_client).await {
+            Ok(_) => {
+                info!("Closed epoch {}", epoch);
+                report.closed_epochs.push(epoch);
+            }
+            Err(err) if err.is::<SlotHistoryRangeError>() => {
+                warn!("Epoch {} can no longer be closed: {}", epoch, err);
+                let error = err.to_string();
+                store_unclosable_epoch(psql_client, epoch, &error).await?;
+                report.unclosable_epochs.push(EpochError { epoch, error });
+            }
+            Err(err) => {
+                error!("Failed to close epoch {}: {}", epoch, err);
+                report.failed_epochs.push(EpochError {
+                    epoch,
+                    error: err.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+pub async fn close_open_epochs_command(
+    options: CloseOpenEpochsOptions,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    info!("Closing open epochs...");
+
+    let (rpc_client, rpc_session) = rpc_client(&options.rpc)?;
+    let report = close_open_epochs(&rpc_client, psql_client).await;
+    write_rpc_session(&options.rpc, &rpc_session)?;
+    let report = report?;
+    serde_yaml::to_writer(std::io::stdout(), &report)?;
+
+    if !report.failed_epochs.is_empty() {
+        anyhow::bail!("Failed to close {} epochs", report.failed_epochs.len());
+    }
+
+    Ok(())
+}
@@ -16,11 +16,18 @@ pub async fn store_cluster_info(
     options: StoreClusterInfoOptions,
     psql_client: &mut Client,
 ) -> anyhow::Result<()> {
-    info!("Storing cluster info...");
-
     let snapshot_file = std::fs::File::open(options.snapshot_path)?;
     let snapshot: ValidatorsPerformanceSnapshot = serde_yaml::from_reader(snapshot_file)?;
 
+    store_cluster_info_snapshot(&snapshot, psql_client).await
+}
+
+pub async fn store_cluster_info_snapshot(
+    snapshot: &ValidatorsPerformanceSnapshot,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    info!("Storing cluster info...");
+
     info!("Loaded the cluster info");
 
     psql_client
@@ -0,0 +1,193 @@
+use collect::common::CommonParams as RpcParams;
+use collect::rpc_service::{rpc_client, write_rpc_session};
+use log::info;
+use rust_decimal::prelude::*;
+use std::collections::HashMap;
+use store::blacklist::{store_blacklist_proposals, BLACKLIST_CODE_COMMISSION_RUG};
+use store::dto::BlacklistRecord;
+use structopt::StructOpt;
+use tokio_postgres::Client;
+
+#[derive(Debug, StructOpt)]
+pub struct DetectCommissionRugsOptions {
+    #[structopt(flatten)]
+    rpc: RpcParams,
+
+    #[structopt(
+        long = "epoch",
+        help = "Closed epoch to check, commissions of the next epoch must be stored"
+    )]
+    epoch: u64,
+
+    #[structopt(
+        long = "end-of-epoch-slots",
+        default_value = "43200",
+        help = "Commission raised within this many slots before the epoch end is suspicious"
+    )]
+    end_of_epoch_slots: u64,
+
+    #[structopt(long = "min-commission-increase", default_value = "5")]
+    min_commission_increase: i32,
+
+    #[structopt(long = "dry-run", help = "Do not store the proposals")]
+    dry_run: bool,
+}
+
+#[derive(Debug)]
+struct CommissionChange {
+    epoch: u64,
+    epoch_slot: u64,
+    commission: i32,
+}
+
+async fn load_commission_changes(
+    psql_client: &Client,
+    from_epoch: u64,
+    to_epoch: u64,
+) -> anyhow::Result<HashMap<String, Vec<CommissionChange>>> {
+    let rows = psql_client
+        .query(
+            "SELECT vote_account, commission, epoch, epoch_slot
+            FROM commissions
+            WHERE epoch BETWEEN $1 AND $2
+            ORDER BY vote_account, epoch, epoch_slot",
+            &[&Decimal::from(from_epoch), &Decimal::from(to_epoch)],
+        )
+        .await?;
+
+    let mut changes: HashMap<_, Vec<_>> = Default::default();
+    for row in rows {
+        changes
+            .entry(row.get("vote_account"))
+            .or_default()
+            .push(CommissionChange {
+                epoch: row.get::<_, Decimal>("epoch").try_into()?,
+                epoch_slot: row.get::<_, Decimal>("epoch_slot").try_into()?,
+                commission: row.get("commission"),
+            });
+    }
+
+    Ok(changes)
+}
+
+async fn load_effective_commissions(
+    psql_client: &Client,
+    epoch: u64,
+) -> anyhow::Result<HashMap<String, i32>> {
+    Ok(psql_client
+        .query(
+            "SELECT vote_account, commission_effective
+            FROM validators
+            WHERE epoch = $1 AND commission_effective IS NOT NULL",
+            &[&Decimal::from(epoch)],
+        )
+        .await?
+        .iter()
+        .map(|row| (row.get("vote_account"), row.get("commission_effective")))
+        .collect())
+}
+
+/// The rug is a commission raised close to the end of the epoch, kept while the rewards are paid
+/// (`commission_effective`) and lowered again in the next epoch.
+fn detect_commission_rug(
+    options: &DetectCommissionRugsOptions,
+    slots_in_epoch: u64,
+    changes: &[CommissionChange],
+    commission_effective: i32,
+) -> Option<String> {
+    let epoch = options.epoch;
+    let suspicious_from_slot = slots_in_epoch.saturating_sub(options.end_of_epoch_slots);
+
+    let commission_before = changes
+        .iter()
+        .rfind(|c| c.epoch < epoch || (c.epoch == epoch && c.epoch_slot < suspicious_from_slot))?
+        .commission;
+    if commission_effective < commission_before + options.min_commission_increase {
+        return None;
+    }
+
+    let lowered = changes.iter().find(|c| {
+        c.epoch == epoch + 1
+            && c.commission <= commission_effective - options.min_commission_increase
+    })?;
+
+    let raised = changes.iter().find(|c| {
+        c.epoch == epoch && c.epoch_slot >= suspicious_from_slot && c.commission > commission_before
+    });
+    let raised_at = match raised {
+        Some(raised) => format!("at slot {} of epoch {}", raised.epoch_slot, epoch),
+        None => format!("after slot {} of epoch {}", suspicious_from_slot, epoch),
+    };
+
+    Some(format!(
+        "Commission raised from {}% {}, rewards of epoch {} paid at {}%, lowered to {}% at slot {} of epoch {}",
+        commission_before,
+        raised_at,
+        epoch,
+        commission_effective,
+        lowered.commission,
+        lowered.epoch_slot,
+        lowered.epoch
+    ))
+}
+
+pub async fn detect_commission_rugs(
+    options: DetectCommissionRugsOptions,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    info!("Detecting commission rugs in epoch {}...", options.epoch);
+
+    let (rpc_client, rpc_session) = rpc_client(&options.rpc)?;
+    let epoch_schedule = rpc_client.get_epoch_schedule().await;
+    write_rpc_session(&options.rpc, &rpc_session)?;
+    let slots_in_epoch = epoch_schedule?.get_slots_in_epoch(options.epoch);
+    info!("Slots in epoch {}: {}", options.epoch, slots_in_epoch);
+
+    let effective_commissions = load_effective_commissions(psql_client, options.epoch).await?;
+    if effective_commissions.is_empty() {
+        anyhow::bail!(
+            "No effective commissions found, epoch {} is not closed yet",
+            options.epoch
+        );
+    }
+    let commission_changes = load_commission_changes(
+        psql_client,
+        options.epoch.saturating_sub(1),
+        options.epoch + 1,
+    )
+    .await?;
+
+    let mut proposals: Vec<_> = effective_commissions
+        .iter()
+        .filter_map(|(vote_account, commission_effective)| {
+            let changes = commission_changes.get(vote_account)?;
+            let evidence =
+                detect_commission_rug(&options, slots_in_epoch, changes, *commission_effective)?;
+            Some(BlacklistRecord {
+                vote_account: vote_account.clone(),
+                code: BLACKLIST_CODE_COMMISSION_RUG.to_string(),
+                evidence: Some(evidence),
+                expires_at: None,
+            })
+        })
+        .collect();
+    proposals.sort_by(|a, b| a.vote_account.cmp(&b.vote_account));
+
+    for proposal in proposals.iter() {
+        info!(
+            "Possible commission rug by {}: {}",
+            proposal.vote_account,
+            proposal.evidence.clone().unwrap_or_default()
+        );
+    }
+
+    if options.dry_run {
+        info!("Dry run, {} proposals not stored", proposals.len());
+        return Ok(());
+    }
+
+    let stored = store_blacklist_proposals(psql_client, options.epoch, &proposals).await?;
+    info!("Stored {} new blacklist proposals", stored);
+
+    Ok(())
+}
@@ -5,6 +5,8 @@ use log::info;
 use rust_decimal::prelude::*;
 use serde_yaml;
 use std::collections::{HashMap, HashSet};
+use store::dto::{NotificationsConfig, ValidatorEvent, ValidatorEventType};
+use store::notifications::{load_notifications_config, notify};
 use structopt::StructOpt;
 use tokio_postgres::types::ToSql;
 use tokio_postgres::Client;
@@ -13,16 +15,35 @@ use tokio_postgres::Client;
 pub struct StoreCommissionsOptions {
     #[structopt(long = "snapshot-file")]
     snapshot_path: String,
+
+    #[structopt(
+        long = "notifications-config",
+        help = "Validator events are sent to the sinks configured in this file"
+    )]
+    notifications_config_path: Option<String>,
 }
 
 pub async fn store_commissions(
     options: StoreCommissionsOptions,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    let notifications_config = match &options.notifications_config_path {
+        Some(path) => load_notifications_config(path)?,
+        None => Default::default(),
+    };
+    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
+    let snapshot: ValidatorsPerformanceSnapshot = serde_yaml::from_reader(snapshot_file)?;
+
+    store_commissions_snapshot(&snapshot, psql_client, &notifications_config).await
+}
+
+pub async fn store_commissions_snapshot(
+    snapshot: &ValidatorsPerformanceSnapshot,
     mut psql_client: &mut Client,
+    notifications_config: &NotificationsConfig,
 ) -> anyhow::Result<()> {
     info!("Storing commission...");
 
-    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
-    let snapshot: ValidatorsPerformanceSnapshot = serde_yaml::from_reader(snapshot_file)?;
     let snapshot_epoch_slot: Decimal = snapshot.epoch_slot.into();
     let snapshot_epoch: Decimal = snapshot.epoch.into();
     let snapshot_created_at = snapshot.created_at.parse::<DateTime<Utc>>().unwrap();
@@ -30,6 +51,7 @@ pub async fn store_commissions(
     info!("Loaded the snapshot");
 
     let mut skipped_vote_accounts: HashSet<String> = Default::default();
+    let mut previous_commissions: HashMap<String, i32> = Default::default();
 
     for row in psql_client
         .query(
@@ -48,6 +70,7 @@ pub async fn store_commissions(
         let vote_account: &str = row.get("vote_account");
         let commission: i32 = row.get("commission");
         let epoch: Decimal = row.get("epoch");
+        previous_commissions.insert(vote_account.to_string(), commission);
 
         if let Some(validator_snapshot) = snapshot.validators.get(vote_account) {
             if epoch == snapshot_epoch && commission == validator_snapshot.commission as i32 {
@@ -67,6 +90,24 @@ pub async fn store_commissions(
         .map(|(i, v)| (i.clone(), v.commission as i32))
         .collect();
 
+    let events: Vec<_> = commissions
+        .iter()
+        .filter_map(|(vote_account, commission)| {
+            let previous_commission = previous_commissions.get(vote_account)?;
+            if previous_commission == commission {
+                return None;
+            }
+            Some(ValidatorEvent {
+                vote_account: vote_account.clone(),
+                event_type: ValidatorEventType::CommissionChange,
+                epoch: snapshot.epoch,
+                value_before: Some(previous_commission.to_string()),
+                value_after: Some(commission.to_string()),
+                created_at: snapshot_created_at,
+            })
+        })
+        .collect();
+
     for (vote_account, commission) in commissions.iter() {
         if !skipped_vote_accounts.contains(vote_account) {
             let mut params: Vec<&(dyn ToSql + Sync)> = vec![
@@ -83,5 +124,7 @@ pub async fn store_commissions(
 
     info!("Stored {} commission changes", insertions.unwrap_or(0));
 
+    notify(psql_client, notifications_config, events).await?;
+
     Ok(())
 }
@@ -0,0 +1,277 @@
+use crate::close_open_epochs::close_open_epochs;
+use crate::cluster_info::store_cluster_info_snapshot;
+use crate::commissions::store_commissions_snapshot;
+use crate::uptime::store_uptime_snapshot;
+use crate::validators::store_validators_snapshot;
+use crate::validators_mev::store_mev_snapshot;
+use crate::versions::store_versions_snapshot;
+use collect::common::CommonParams as RpcParams;
+use collect::rpc_service::{rpc_client, write_rpc_session, RpcSession};
+use collect::validators::{validators_snapshot, ValidatorsOptions};
+use collect::validators_mev::validators_mev_snapshot;
+use collect::validators_performance::{
+    validators_performance_snapshot, ValidatorsPerformanceOptions,
+};
+use collect::whois_service::IpInfoOptions;
+use log::{error, info, warn};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use store::dto::NotificationsConfig;
+use store::notifications::load_notifications_config;
+use structopt::StructOpt;
+use tokio::time::{sleep, sleep_until, Duration, Instant};
+use tokio_postgres::Client;
+
+#[derive(Debug, StructOpt)]
+pub struct DaemonOptions {
+    #[structopt(flatten)]
+    rpc: RpcParams,
+
+    #[structopt(flatten)]
+    ip_info: IpInfoOptions,
+
+    #[structopt(
+        long = "marinade-config",
+        help = "Marinade state and stake authorities, mainnet is used by default."
+    )]
+    marinade_config_path: Option<String>,
+
+    #[structopt(long = "escrow-relocker", help = "Escrow relocker program address.")]
+    escrow_relocker: Option<Pubkey>,
+
+    #[structopt(long = "gauge-meister", help = "Gauge meister of the vote gauges.")]
+    gauge_meister: Option<Pubkey>,
+
+    #[structopt(long = "notifications-config")]
+    notifications_config_path: Option<String>,
+
+    #[structopt(
+        long = "performance-interval",
+        default_value = "300",
+        help = "Seconds between the snapshots of uptimes, commissions, versions and cluster info"
+    )]
+    performance_interval: u64,
+
+    #[structopt(
+        long = "validators-interval",
+        default_value = "3600",
+        help = "Seconds between the snapshots of validators"
+    )]
+    validators_interval: u64,
+
+    #[structopt(
+        long = "mev-interval",
+        default_value = "3600",
+        help = "Seconds between the snapshots of validators MEV"
+    )]
+    mev_interval: u64,
+
+    #[structopt(
+        long = "close-epochs-interval",
+        default_value = "1800",
+        help = "Seconds between the checks for open epochs to close"
+    )]
+    close_epochs_interval: u64,
+
+    #[structopt(
+        long = "epoch-close-delay",
+        default_value = "600",
+        help = "Seconds to wait after the epoch start before closing the previous epoch, so the rewards are paid out"
+    )]
+    epoch_close_delay: u64,
+
+    #[structopt(long = "max-retries", default_value = "3")]
+    max_retries: u32,
+
+    #[structopt(
+        long = "retry-delay",
+        default_value = "30",
+        help = "Seconds before the first retry, the delay grows with each attempt"
+    )]
+    retry_delay: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Job {
+    Performance,
+    Validators,
+    Mev,
+    CloseEpochs,
+}
+
+struct ScheduledJob {
+    job: Job,
+    interval: Duration,
+    next_run_at: Instant,
+}
+
+struct Daemon {
+    options: DaemonOptions,
+    rpc_client: RpcClient,
+    rpc_session: RpcSession,
+    notifications_config: NotificationsConfig,
+    last_epoch: Option<u64>,
+}
+
+impl Daemon {
+    /// Returns the epoch of the collected snapshot
+    async fn store_performance(&self, psql_client: &mut Client) -> anyhow::Result<u64> {
+        let snapshot = validators_performance_snapshot(
+            &self.rpc_client,
+            &ValidatorsPerformanceOptions {
+                with_rewards: false,
+                epoch: None,
+            },
+        )
+        .await?;
+
+        store_uptime_snapshot(&snapshot, psql_client, &self.notifications_config).await?;
+        store_commissions_snapshot(&snapshot, psql_client, &self.notifications_config).await?;
+        store_versions_snapshot(&snapshot, psql_client, &self.notifications_config).await?;
+        store_cluster_info_snapshot(&snapshot, psql_client).await?;
+
+        Ok(snapshot.epoch)
+    }
+
+    async fn store_validators(&self, psql_client: &mut Client) -> anyhow::Result<()> {
+        let options = ValidatorsOptions {
+            gauge_meister: self.options.gauge_meister,
+            escrow_relocker: self.options.escrow_relocker,
+            ip_info: self.options.ip_info.clone(),
+            marinade_config_path: self.options.marinade_config_path.clone(),
+            epoch: None,
+        };
+        let snapshot = validators_snapshot(&self.rpc_client, &options).await?;
+
+        store_validators_snapshot(&snapshot, psql_client, &self.notifications_config).await
+    }
+
+    async fn store_mev(&self, psql_client: &mut Client) -> anyhow::Result<()> {
+        let snapshot = validators_mev_snapshot(&self.rpc_client).await?;
+
+        store_mev_snapshot(&snapshot, psql_client).await
+    }
+
+    async fn close_epochs(&self, psql_client: &mut Client) -> anyhow::Result<()> {
+        let report = close_open_epochs(&self.rpc_client, psql_client).await?;
+        if !report.unclosable_epochs.is_empty() {
+            error!(
+                "Epochs that can no longer be closed: {:?}",
+                report.unclosable_epochs
+            );
+        }
+        if !report.failed_epochs.is_empty() {
+            anyhow::bail!("Failed to close epochs: {:?}", report.failed_epochs);
+        }
+
+        Ok(())
+    }
+
+    async fn run(&mut self, job: Job, psql_client: &mut Client) -> anyhow::Result<()> {
+        let result = match job {
+            Job::Performance => self
+                .store_performance(psql_client)
+                .await
+                .map(|epoch| self.last_epoch = Some(epoch)),
+            Job::Validators => self.store_validators(psql_client).await,
+            Job::Mev => self.store_mev(psql_client).await,
+            Job::CloseEpochs => self.close_epochs(psql_client).await,
+        };
+        if let Err(err) = write_rpc_session(&self.options.rpc, &self.rpc_session) {
+            warn!("Failed to write the RPC report: {}", err);
+        }
+
+        result
+    }
+
+    async fn run_with_retries(&mut self, job: Job, psql_client: &mut Client) -> anyhow::Result<()> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match self.run(job, psql_client).await {
+                Ok(_) => return Ok(()),
+                Err(err) if attempt <= self.options.max_retries => {
+                    let delay = Duration::from_secs(self.options.retry_delay * attempt as u64);
+                    warn!(
+                        "Job {:?} failed (attempt {}), retrying in {:?}: {}",
+                        job, attempt, delay, err
+                    );
+                    sleep(delay).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+pub async fn daemon(options: DaemonOptions, psql_client: &mut Client) -> anyhow::Result<()> {
+    info!("Starting the daemon...");
+
+    let notifications_config = match &options.notifications_config_path {
+        Some(path) => load_notifications_config(path)?,
+        None => Default::default(),
+    };
+    let now = Instant::now();
+    let mut schedule: Vec<_> = [
+        (Job::Performance, options.performance_interval),
+        (Job::Validators, options.validators_interval),
+        (Job::Mev, options.mev_interval),
+        (Job::CloseEpochs, options.close_epochs_interval),
+    ]
+    .into_iter()
+    .map(|(job, interval)| ScheduledJob {
+        job,
+        interval: Duration::from_secs(interval),
+        next_run_at: now,
+    })
+    .collect();
+    let (rpc_client, rpc_session) = rpc_client(&options.rpc)?;
+    let mut daemon = Daemon {
+        rpc_client,
+        rpc_session,
+        options,
+        notifications_config,
+        last_epoch: None,
+    };
+
+    loop {
+        let scheduled_job = schedule
+            .iter_mut()
+            .min_by_key(|scheduled_job| scheduled_job.next_run_at)
+            .unwrap();
+
+        tokio::select! {
+            _ = sleep_until(scheduled_job.next_run_at) => {}
+            _ = tokio::signal::ctrl_c() => {
+                info!("Stopping the daemon");
+                return Ok(());
+            }
+        }
+
+        let job = scheduled_job.job;
+        scheduled_job.next_run_at = Instant::now() + scheduled_job.interval;
+
+        let previous_epoch = daemon.last_epoch;
+        info!("Running job {:?}", job);
+        if let Err(err) = daemon.run_with_retries(job, psql_client).await {
+            error!("Job {:?} failed: {}", job, err);
+        }
+
+        // The new epoch needs fresh validator records and the previous one can be closed
+        if let (Some(previous_epoch), Some(epoch)) = (previous_epoch, daemon.last_epoch) {
+            if epoch > previous_epoch {
+                info!("New epoch {} started", epoch);
+                let now = Instant::now();
+                for scheduled_job in schedule.iter_mut() {
+                    scheduled_job.next_run_at = match scheduled_job.job {
+                        Job::Performance => continue,
+                        Job::CloseEpochs => {
+                            now + Duration::from_secs(daemon.options.epoch_close_delay)
+                        }
+                        _ => now,
+                    };
+                }
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@ use collect::validators::{ValidatorDataCenter, ValidatorSnapshot};
 use collect::validators_mev::ValidatorMEVSnapshot;
 use rust_decimal::prelude::*;
 use serde::de::{self, Unexpected};
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::{HashMap, HashSet};
 
 pub struct ValidatorMEVInfo {
@@ -145,8 +145,14 @@ pub struct ValidatorEpochStats {
     pub uptime_pct: Option<f64>,
     pub uptime: Option<u64>,
     pub downtime: Option<u64>,
+    pub vote_latency: Option<f64>,
+    pub vote_credits_ratio: Option<f64>,
+    pub mev_commission: Option<u16>,
+    pub mev_rewards: Option<u64>,
     pub apr: Option<f64>,
     pub apy: Option<f64>,
+    pub apr_with_mev: Option<f64>,
+    pub apy_with_mev: Option<f64>,
     pub score: Option<f64>,
     pub rank_score: Option<usize>,
     pub rank_activated_stake: Option<usize>,
@@ -194,6 +200,7 @@ pub struct ValidatorRecord {
     pub has_last_epoch_stats: bool,
     pub avg_uptime_pct: Option<f64>,
     pub avg_apy: Option<f64>,
+    pub avg_apy_with_mev: Option<f64>,
 }
 
 #[derive(Serialize, Debug, Clone, utoipa::ToSchema)]
@@ -211,6 +218,18 @@ pub struct VersionRecord {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct MevRecord {
+    pub epoch: u64,
+    pub epoch_slot: u64,
+    pub mev_commission: u16,
+    pub total_epoch_rewards: Option<u64>,
+    pub claimed_epoch_rewards: Option<u64>,
+    pub total_epoch_claimants: Option<u32>,
+    pub epoch_active_claimants: Option<u32>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Serialize, Debug, Clone, utoipa::ToSchema)]
 pub struct CommissionRecord {
     pub epoch: u64,
@@ -246,10 +265,82 @@ pub struct BlockProductionStats {
     pub avg_skip_rate: f64,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, Default, utoipa::ToSchema)]
+pub struct DecentralizationIndices {
+    /// Number of the biggest entities holding together more than 1/3 of the stake
+    pub nakamoto_coefficient: u64,
+    /// Sum of the squared stake shares, 1 when all the stake is held by a single entity
+    pub herfindahl_hirschman_index: f64,
+    /// 0 for equally staked entities, close to 1 when the stake is held by a single entity
+    pub gini_coefficient: f64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, utoipa::ToSchema)]
+pub struct DecentralizationIndicesChange {
+    pub nakamoto_coefficient: i64,
+    pub herfindahl_hirschman_index: f64,
+    pub gini_coefficient: f64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, utoipa::ToSchema)]
+pub struct DecentralizationBreakdown {
+    pub indices: DecentralizationIndices,
+    pub indices_without_marinade: DecentralizationIndices,
+    /// Change of the indices caused by the Marinade stake
+    pub marinade_impact: DecentralizationIndicesChange,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, utoipa::ToSchema)]
+pub struct DecentralizationChange {
+    pub indices_before: DecentralizationIndices,
+    pub indices_after: DecentralizationIndices,
+    pub change: DecentralizationIndicesChange,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct ConcentrationChange {
+    pub key: String,
+    pub concentration_before: f64,
+    pub concentration_after: f64,
+}
+
+/// Change of the cluster decentralization when the stakes change
+#[derive(Deserialize, Serialize, Debug, Clone, Default, utoipa::ToSchema)]
+pub struct DecentralizationImpact {
+    pub by_validator: DecentralizationChange,
+    pub by_aso: DecentralizationChange,
+    pub by_country: DecentralizationChange,
+    pub by_city: DecentralizationChange,
+    /// Only the changed concentrations, the biggest changes come first
+    pub concentration_changes_by_aso: Vec<ConcentrationChange>,
+    pub concentration_changes_by_country: Vec<ConcentrationChange>,
+    pub concentration_changes_by_city: Vec<ConcentrationChange>,
+    pub validators_entering_superminority: u64,
+    pub validators_leaving_superminority: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, utoipa::ToSchema)]
+pub struct DecentralizationStats {
+    pub epoch: u64,
+    pub by_validator: DecentralizationBreakdown,
+    pub by_aso: DecentralizationBreakdown,
+    pub by_country: DecentralizationBreakdown,
+    pub by_city: DecentralizationBreakdown,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
 pub struct ClusterStats {
     pub block_production_stats: Vec<BlockProductionStats>,
     pub dc_concentration_stats: Vec<DCConcentrationStats>,
+    pub decentralization_stats: Vec<DecentralizationStats>,
+}
+
+/// Cluster aggregates of a single epoch, stored when the epoch is closed
+#[derive(Debug, Clone)]
+pub struct EpochClusterStats {
+    pub block_production: BlockProductionStats,
+    pub dc_concentration: DCConcentrationStats,
+    pub decentralization: DecentralizationStats,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
@@ -269,6 +360,7 @@ pub struct ValidatorAggregatedFlat {
     pub avg_grace_skip_rate: f64,
     pub max_commission: u8,
     pub avg_adjusted_credits: f64,
+    pub avg_vote_latency: Option<f64>,
     pub dc_aso: String,
     pub mnde_votes: u64,
     pub marinade_stake: f64,
@@ -282,11 +374,11 @@ pub struct ValidatorScoringCsvRow {
     pub rank: i32,
     pub mnde_votes: Decimal,
     pub ui_hints: String,
-    #[serde(deserialize_with = "bool_from_int")]
+    #[serde(deserialize_with = "bool_from_int", serialize_with = "bool_to_int")]
     pub eligible_stake_algo: bool,
-    #[serde(deserialize_with = "bool_from_int")]
+    #[serde(deserialize_with = "bool_from_int", serialize_with = "bool_to_int")]
     pub eligible_stake_mnde: bool,
-    #[serde(deserialize_with = "bool_from_int")]
+    #[serde(deserialize_with = "bool_from_int", serialize_with = "bool_to_int")]
     pub eligible_stake_msol: bool,
     pub normalized_dc_concentration: f64,
     pub normalized_grace_skip_rate: f64,
@@ -297,6 +389,12 @@ pub struct ValidatorScoringCsvRow {
     pub rank_dc_concentration: i32,
     pub rank_grace_skip_rate: i32,
     pub rank_adjusted_credits: i32,
+    #[serde(default)]
+    pub normalized_vote_latency: f64,
+    #[serde(default)]
+    pub avg_vote_latency: Option<f64>,
+    #[serde(default)]
+    pub rank_vote_latency: i32,
     pub target_stake_algo: Decimal,
     pub target_stake_mnde: Decimal,
     pub target_stake_msol: Decimal,
@@ -321,6 +419,32 @@ pub struct ValidatorScoreRecord {
     pub scoring_run_id: i64,
 }
 
+#[derive(Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct ValidatorScoreDiff {
+    pub vote_account: String,
+    pub rank_before: Option<i32>,
+    pub rank_after: Option<i32>,
+    pub rank_change: Option<i32>,
+    pub score_before: Option<f64>,
+    pub score_after: Option<f64>,
+    pub score_delta: Option<f64>,
+    pub component_score_deltas: Vec<Option<f64>>,
+    pub eligible_stake_algo_before: Option<bool>,
+    pub eligible_stake_algo_after: Option<bool>,
+    pub eligible_stake_mnde_before: Option<bool>,
+    pub eligible_stake_mnde_after: Option<bool>,
+    pub eligible_stake_msol_before: Option<bool>,
+    pub eligible_stake_msol_after: Option<bool>,
+    pub target_stake_algo_before: u64,
+    pub target_stake_algo_after: u64,
+    pub target_stake_mnde_before: u64,
+    pub target_stake_mnde_after: u64,
+    pub target_stake_msol_before: u64,
+    pub target_stake_msol_after: u64,
+    pub target_stake_before: u64,
+    pub target_stake_after: u64,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ScoringRunRecord {
     pub scoring_run_id: Decimal,
@@ -329,6 +453,169 @@ pub struct ScoringRunRecord {
     pub components: Vec<String>,
     pub component_weights: Vec<f64>,
     pub ui_id: String,
+    pub config: Option<ScoringConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct ScoringConfig {
+    pub version: u32,
+    pub total_stake: f64,
+    pub marinade_validators_count: usize,
+    pub weights: ScoringWeights,
+    pub eligibility: ScoringEligibility,
+    pub stake_control: StakeControlSplit,
+    pub mnde_validator_cap: f64,
+}
+
+/// Inputs of a scoring run besides the config, validators are loaded for `epochs` epochs up to `epoch`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ScoringInputs {
+    pub epoch: u64,
+    pub epochs: u64,
+    pub blacklist: HashMap<String, HashSet<String>>,
+    pub msol_votes: HashMap<String, f64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct ScoringWeights {
+    pub adjusted_credits: f64,
+    pub grace_skip_rate: f64,
+    pub dc_concentration: f64,
+    /// Candidate component, left out of the scoring while the weight is 0
+    #[serde(default)]
+    pub vote_latency: f64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct ScoringEligibility {
+    pub min_version: String,
+    pub algo_stake: StakeEligibility,
+    pub mnde_stake: StakeEligibility,
+    pub msol_stake: StakeEligibility,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct StakeEligibility {
+    pub max_commission: u8,
+    pub min_stake: f64,
+    pub score_threshold_multiplier: Option<f64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct StakeControlSplit {
+    pub mnde: f64,
+    pub msol: f64,
+}
+
+/// Rules producing the unstake hints, rules missing in the config file fall back to the defaults
+#[derive(Deserialize, Serialize, Debug, Clone, Default, utoipa::ToSchema)]
+#[serde(default)]
+pub struct UnstakeHintsConfig {
+    pub high_commission: HighCommissionRule,
+    pub low_credits: LowCreditsRule,
+    pub blacklist: BlacklistRule,
+    pub low_uptime: LowUptimeRule,
+    pub outdated_version: OutdatedVersionRule,
+    pub superminority: SuperminorityRule,
+    pub high_mev_commission: HighMevCommissionRule,
+}
+
+/// `HighCommission` uses the current epoch, `HighCommissionInPreviousEpoch` the `look_back_epochs` epochs before it
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+#[serde(default)]
+pub struct HighCommissionRule {
+    pub enabled: bool,
+    pub max_commission: u8,
+    pub look_back_epochs: u64,
+}
+
+impl Default for HighCommissionRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_commission: 10,
+            look_back_epochs: 1,
+        }
+    }
+}
+
+/// Credits relative to the stake-weighted average, averaged over the last `look_back_epochs` epochs
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+#[serde(default)]
+pub struct LowCreditsRule {
+    pub enabled: bool,
+    pub min_credits_performance: f64,
+    pub look_back_epochs: u64,
+}
+
+impl Default for LowCreditsRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_credits_performance: 0.5,
+            look_back_epochs: 1,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+#[serde(default)]
+pub struct BlacklistRule {
+    pub enabled: bool,
+}
+
+impl Default for BlacklistRule {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Uptime (0-1) averaged over the `look_back_epochs` closed epochs before the current one
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+#[serde(default)]
+pub struct LowUptimeRule {
+    pub enabled: bool,
+    pub min_uptime_pct: f64,
+    pub look_back_epochs: u64,
+}
+
+impl Default for LowUptimeRule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_uptime_pct: 0.9,
+            look_back_epochs: 1,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, utoipa::ToSchema)]
+#[serde(default)]
+pub struct OutdatedVersionRule {
+    pub enabled: bool,
+    pub min_version: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, utoipa::ToSchema)]
+#[serde(default)]
+pub struct SuperminorityRule {
+    pub enabled: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+#[serde(default)]
+pub struct HighMevCommissionRule {
+    pub enabled: bool,
+    pub max_mev_commission_bps: u16,
+}
+
+impl Default for HighMevCommissionRule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_mev_commission_bps: 1000,
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, Hash, Eq, PartialEq)]
@@ -337,6 +624,39 @@ pub enum UnstakeHint {
     HighCommissionInPreviousEpoch,
     Blacklist,
     LowCredits,
+    LowUptime,
+    OutdatedVersion,
+    Superminority,
+    HighMevCommission,
+}
+
+impl UnstakeHint {
+    pub fn code(&self) -> &'static str {
+        match self {
+            UnstakeHint::HighCommission => "HighCommission",
+            UnstakeHint::HighCommissionInPreviousEpoch => "HighCommissionInPreviousEpoch",
+            UnstakeHint::Blacklist => "Blacklist",
+            UnstakeHint::LowCredits => "LowCredits",
+            UnstakeHint::LowUptime => "LowUptime",
+            UnstakeHint::OutdatedVersion => "OutdatedVersion",
+            UnstakeHint::Superminority => "Superminority",
+            UnstakeHint::HighMevCommission => "HighMevCommission",
+        }
+    }
+
+    pub fn from_code(code: &str) -> anyhow::Result<Self> {
+        Ok(match code {
+            "HighCommission" => UnstakeHint::HighCommission,
+            "HighCommissionInPreviousEpoch" => UnstakeHint::HighCommissionInPreviousEpoch,
+            "Blacklist" => UnstakeHint::Blacklist,
+            "LowCredits" => UnstakeHint::LowCredits,
+            "LowUptime" => UnstakeHint::LowUptime,
+            "OutdatedVersion" => UnstakeHint::OutdatedVersion,
+            "Superminority" => UnstakeHint::Superminority,
+            "HighMevCommission" => UnstakeHint::HighMevCommission,
+            _ => anyhow::bail!("Unknown unstake hint: {}", code),
+        })
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
@@ -346,10 +666,141 @@ pub struct UnstakeHintRecord {
     pub hints: HashSet<UnstakeHint>
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct UnstakeHintHistoryRecord {
+    pub vote_account: String,
+    pub hint: UnstakeHint,
+    pub first_seen_epoch: u64,
+    pub last_seen_epoch: u64,
+    pub active: bool,
+}
+
+/// Row of `blacklist.csv`, evidence and expiration are optional columns
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct BlacklistRecord {
     pub vote_account: String,
     pub code: String,
+    #[serde(default)]
+    pub evidence: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct BlacklistEntry {
+    pub vote_account: String,
+    pub code: String,
+    pub evidence: Option<String>,
+    pub added_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct BlacklistProposalRecord {
+    pub blacklist_proposal_id: i64,
+    pub vote_account: String,
+    pub code: String,
+    pub epoch: u64,
+    pub evidence: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct BlacklistAuditRecord {
+    pub blacklist_audit_id: i64,
+    pub action: String,
+    pub vote_account: String,
+    pub code: String,
+    pub evidence: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Hash, Eq, PartialEq, utoipa::ToSchema)]
+pub enum ValidatorEventType {
+    DowntimeStart,
+    DowntimeEnd,
+    CommissionChange,
+    VersionChange,
+    SuperminorityEnter,
+    SuperminorityLeave,
+}
+
+impl ValidatorEventType {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValidatorEventType::DowntimeStart => "DowntimeStart",
+            ValidatorEventType::DowntimeEnd => "DowntimeEnd",
+            ValidatorEventType::CommissionChange => "CommissionChange",
+            ValidatorEventType::VersionChange => "VersionChange",
+            ValidatorEventType::SuperminorityEnter => "SuperminorityEnter",
+            ValidatorEventType::SuperminorityLeave => "SuperminorityLeave",
+        }
+    }
+
+    pub fn from_code(code: &str) -> anyhow::Result<Self> {
+        Ok(match code {
+            "DowntimeStart" => ValidatorEventType::DowntimeStart,
+            "DowntimeEnd" => ValidatorEventType::DowntimeEnd,
+            "CommissionChange" => ValidatorEventType::CommissionChange,
+            "VersionChange" => ValidatorEventType::VersionChange,
+            "SuperminorityEnter" => ValidatorEventType::SuperminorityEnter,
+            "SuperminorityLeave" => ValidatorEventType::SuperminorityLeave,
+            _ => anyhow::bail!("Unknown validator event type: {}", code),
+        })
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ValidatorEvent {
+    pub vote_account: String,
+    pub event_type: ValidatorEventType,
+    pub epoch: u64,
+    pub value_before: Option<String>,
+    pub value_after: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, utoipa::ToSchema)]
+pub struct ValidatorEventRecord {
+    pub validator_event_id: i64,
+    pub vote_account: String,
+    pub event_type: ValidatorEventType,
+    pub epoch: u64,
+    pub value_before: Option<String>,
+    pub value_after: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    pub sinks: Vec<NotificationSink>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationSinkKind {
+    Webhook {
+        url: String,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
+    Log,
+}
+
+/// Sink receiving the events, empty `vote_accounts` or `event_types` subscribe to all of them
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NotificationSink {
+    #[serde(flatten)]
+    pub kind: NotificationSinkKind,
+    #[serde(default)]
+    pub vote_accounts: HashSet<String>,
+    #[serde(default)]
+    pub event_types: HashSet<ValidatorEventType>,
 }
 
 fn bool_from_int<'de, D>(deserializer: D) -> Result<bool, D::Error>
@@ -365,3 +816,10 @@ where
         )),
     }
 }
+
+fn bool_to_int<S>(value: &bool, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_u8(*value as u8)
+}
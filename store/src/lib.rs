@@ -1,3 +1,6 @@
+pub mod blacklist;
 pub mod dto;
+pub mod notifications;
 pub mod utils;
 pub mod scoring;
+pub mod scoring_engine;
@@ -6,27 +6,36 @@ use tokio_postgres::Client;
 #[derive(Debug, StructOpt)]
 pub struct LsOpenEpochsOptions {}
 
-pub async fn list_open_epochs(psql_client: &Client) -> anyhow::Result<()> {
-    info!("Finding open epochs...");
-
-    let rows = psql_client
+/// Epochs recorded as unclosable are left out, they would be collected again in vain
+pub async fn load_open_epochs(psql_client: &Client) -> anyhow::Result<Vec<u64>> {
+    psql_client
         .query(
             "
         SELECT DISTINCT epoch
         FROM validators
         WHERE epoch NOT IN (SELECT DISTINCT epoch FROM epochs)
+            AND epoch NOT IN (SELECT epoch FROM unclosable_epochs)
+        ORDER BY epoch
     ",
             &[],
         )
-        .await?;
+        .await?
+        .iter()
+        .map(|row| Ok(row.get::<_, Decimal>("epoch").try_into()?))
+        .collect()
+}
+
+pub async fn list_open_epochs(psql_client: &Client) -> anyhow::Result<()> {
+    info!("Finding open epochs...");
+
+    let open_epochs = load_open_epochs(psql_client).await?;
 
-    for row in rows.iter() {
-        let epoch: Decimal = row.get("epoch");
+    for epoch in open_epochs.iter() {
         println!("{}", epoch);
         info!("Open epoch: {}", epoch);
     }
 
-    info!("Found open epochs: {}", rows.len());
+    info!("Found open epochs: {}", open_epochs.len());
 
     Ok(())
 }
@@ -1,10 +1,18 @@
+use backfill_cluster_stats::{backfill_cluster_stats, BackfillClusterStatsOptions};
+use blacklist_import::{blacklist_import, BlacklistImportOptions};
 use close_epoch::{close_epoch, CloseEpochOptions};
+use close_open_epochs::{close_open_epochs_command, CloseOpenEpochsOptions};
 use cluster_info::{store_cluster_info, StoreClusterInfoOptions};
+use commission_rugs::{detect_commission_rugs, DetectCommissionRugsOptions};
 use commissions::{store_commissions, StoreCommissionsOptions};
+use daemon::{daemon, DaemonOptions};
 use env_logger::Env;
 use ls_open_epochs::{list_open_epochs, LsOpenEpochsOptions};
+use notify_test::{notify_test, NotifyTestOptions};
+use scoring_run::{scoring_run, ScoringRunOptions};
 use structopt::StructOpt;
 use tokio_postgres::NoTls;
+use unstake_hints::{store_unstake_hints_snapshot, StoreUnstakeHintsOptions};
 use uptime::{store_uptime, StoreUptimeOptions};
 use validators::{store_validators, StoreValidatorsOptions};
 use validators_mev::{store_mev, StoreMevOptions};
@@ -35,13 +43,29 @@ enum StoreCommand {
     ValidatorsMev(StoreMevOptions),
     CloseEpoch(CloseEpochOptions),
     LsOpenEpochs(LsOpenEpochsOptions),
+    CloseOpenEpochs(CloseOpenEpochsOptions),
+    BackfillClusterStats(BackfillClusterStatsOptions),
+    ScoringRun(ScoringRunOptions),
+    BlacklistImport(BlacklistImportOptions),
+    DetectCommissionRugs(DetectCommissionRugsOptions),
+    UnstakeHints(StoreUnstakeHintsOptions),
+    NotifyTest(NotifyTestOptions),
+    Daemon(Box<DaemonOptions>),
 }
 
+pub mod backfill_cluster_stats;
+pub mod blacklist_import;
 pub mod close_epoch;
+pub mod close_open_epochs;
 pub mod cluster_info;
+pub mod commission_rugs;
 pub mod commissions;
+pub mod daemon;
 pub mod dto;
 pub mod ls_open_epochs;
+pub mod notify_test;
+pub mod scoring_run;
+pub mod unstake_hints;
 pub mod uptime;
 pub mod utils;
 pub mod validators;
@@ -71,5 +95,21 @@ async fn main() -> anyhow::Result<()> {
         StoreCommand::ValidatorsMev(options) => store_mev(options, &mut psql_client).await,
         StoreCommand::CloseEpoch(options) => close_epoch(options, &mut psql_client).await,
         StoreCommand::LsOpenEpochs(_options) => list_open_epochs(&psql_client).await,
+        StoreCommand::CloseOpenEpochs(options) => {
+            close_open_epochs_command(options, &mut psql_client).await
+        }
+        StoreCommand::BackfillClusterStats(_options) => {
+            backfill_cluster_stats(&mut psql_client).await
+        }
+        StoreCommand::ScoringRun(options) => scoring_run(options, &mut psql_client).await,
+        StoreCommand::BlacklistImport(options) => blacklist_import(options, &mut psql_client).await,
+        StoreCommand::DetectCommissionRugs(options) => {
+            detect_commission_rugs(options, &mut psql_client).await
+        }
+        StoreCommand::UnstakeHints(options) => {
+            store_unstake_hints_snapshot(options, &mut psql_client).await
+        }
+        StoreCommand::NotifyTest(options) => notify_test(options).await,
+        StoreCommand::Daemon(options) => daemon(*options, &mut psql_client).await,
     }?)
 }
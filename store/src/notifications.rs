@@ -0,0 +1,175 @@
+use crate::dto::{
+    NotificationSink, NotificationSinkKind, NotificationsConfig, ValidatorEvent,
+    ValidatorEventRecord, ValidatorEventType,
+};
+use rust_decimal::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio_postgres::{Client, Row};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    events: Vec<&'a ValidatorEventRecord>,
+}
+
+pub fn load_notifications_config(config_path: &String) -> anyhow::Result<NotificationsConfig> {
+    Ok(serde_yaml::from_reader(std::fs::File::open(config_path)?)?)
+}
+
+impl NotificationSink {
+    pub fn accepts(&self, event: &ValidatorEventRecord) -> bool {
+        (self.vote_accounts.is_empty() || self.vote_accounts.contains(&event.vote_account))
+            && (self.event_types.is_empty() || self.event_types.contains(&event.event_type))
+    }
+}
+
+pub async fn store_validator_events(
+    psql_client: &mut Client,
+    events: &[ValidatorEvent],
+) -> anyhow::Result<Vec<ValidatorEventRecord>> {
+    let transaction = psql_client.transaction().await?;
+    let mut records = Vec::with_capacity(events.len());
+    for event in events {
+        let row = transaction
+            .query_one(
+                "INSERT INTO validator_events (vote_account, event_type, epoch, value_before, value_after, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING validator_event_id",
+                &[
+                    &event.vote_account,
+                    &event.event_type.code(),
+                    &Decimal::from(event.epoch),
+                    &event.value_before,
+                    &event.value_after,
+                    &event.created_at,
+                ],
+            )
+            .await?;
+        records.push(ValidatorEventRecord {
+            validator_event_id: row.get("validator_event_id"),
+            vote_account: event.vote_account.clone(),
+            event_type: event.event_type,
+            epoch: event.epoch,
+            value_before: event.value_before.clone(),
+            value_after: event.value_after.clone(),
+            created_at: event.created_at,
+        });
+    }
+    transaction.commit().await?;
+
+    Ok(records)
+}
+
+fn validator_event_from_row(row: &Row) -> anyhow::Result<ValidatorEventRecord> {
+    Ok(ValidatorEventRecord {
+        validator_event_id: row.get("validator_event_id"),
+        vote_account: row.get("vote_account"),
+        event_type: ValidatorEventType::from_code(row.get("event_type"))?,
+        epoch: row.get::<_, Decimal>("epoch").try_into()?,
+        value_before: row.get("value_before"),
+        value_after: row.get("value_after"),
+        created_at: row.get("created_at"),
+    })
+}
+
+pub async fn load_last_validator_event_id(psql_client: &Client) -> anyhow::Result<i64> {
+    Ok(psql_client
+        .query_one(
+            "SELECT COALESCE(MAX(validator_event_id), 0) AS validator_event_id FROM validator_events",
+            &[],
+        )
+        .await?
+        .get("validator_event_id"))
+}
+
+pub async fn load_validator_events_after(
+    psql_client: &Client,
+    validator_event_id: i64,
+) -> anyhow::Result<Vec<ValidatorEventRecord>> {
+    psql_client
+        .query(
+            "SELECT validator_event_id, vote_account, event_type, epoch, value_before, value_after, created_at
+            FROM validator_events
+            WHERE validator_event_id > $1
+            ORDER BY validator_event_id",
+            &[&validator_event_id],
+        )
+        .await?
+        .iter()
+        .map(validator_event_from_row)
+        .collect()
+}
+
+async fn send_webhook(
+    http_client: &reqwest::Client,
+    url: &String,
+    headers: &HashMap<String, String>,
+    events: Vec<&ValidatorEventRecord>,
+) -> anyhow::Result<()> {
+    let mut request = http_client
+        .post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(&WebhookPayload { events });
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    request.send().await?.error_for_status()?;
+
+    Ok(())
+}
+
+/// Delivers the events to all subscribed sinks, failing sinks are only logged
+pub async fn dispatch_validator_events(
+    config: &NotificationsConfig,
+    events: &[ValidatorEventRecord],
+) {
+    let http_client = reqwest::Client::new();
+
+    for sink in config.sinks.iter() {
+        let events: Vec<_> = events.iter().filter(|event| sink.accepts(event)).collect();
+        if events.is_empty() {
+            continue;
+        }
+
+        match &sink.kind {
+            NotificationSinkKind::Webhook { url, headers } => {
+                let count = events.len();
+                match send_webhook(&http_client, url, headers, events).await {
+                    Ok(_) => log::info!("Sent {} events to {}", count, url),
+                    Err(err) => log::error!("Failed to send {} events to {}: {}", count, url, err),
+                }
+            }
+            NotificationSinkKind::Log => {
+                for event in events {
+                    log::info!(
+                        "Validator event {:?} of {}: {:?} -> {:?}",
+                        event.event_type,
+                        event.vote_account,
+                        event.value_before,
+                        event.value_after
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Stores the events so they can be served by the API and sends them to the configured sinks
+pub async fn notify(
+    psql_client: &mut Client,
+    config: &NotificationsConfig,
+    events: Vec<ValidatorEvent>,
+) -> anyhow::Result<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let records = store_validator_events(psql_client, &events).await?;
+    log::info!("Stored {} validator events", records.len());
+    dispatch_validator_events(config, &records).await;
+
+    Ok(())
+}
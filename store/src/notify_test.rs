@@ -0,0 +1,36 @@
+use chrono::Utc;
+use log::info;
+use store::dto::{ValidatorEventRecord, ValidatorEventType};
+use store::notifications::{dispatch_validator_events, load_notifications_config};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct NotifyTestOptions {
+    #[structopt(long = "notifications-config")]
+    notifications_config_path: String,
+
+    #[structopt(long = "vote-account")]
+    vote_account: String,
+
+    #[structopt(long = "event-type", default_value = "CommissionChange")]
+    event_type: String,
+}
+
+/// Sends a sample event to the configured sinks without storing it
+pub async fn notify_test(options: NotifyTestOptions) -> anyhow::Result<()> {
+    let config = load_notifications_config(&options.notifications_config_path)?;
+    let event = ValidatorEventRecord {
+        validator_event_id: 0,
+        vote_account: options.vote_account,
+        event_type: ValidatorEventType::from_code(&options.event_type)?,
+        epoch: 0,
+        value_before: Some("before".to_string()),
+        value_after: Some("after".to_string()),
+        created_at: Utc::now(),
+    };
+
+    info!("Sending a test event to {} sinks...", config.sinks.len());
+    dispatch_validator_events(&config, &[event]).await;
+
+    Ok(())
+}
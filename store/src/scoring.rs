@@ -1,33 +1,63 @@
-use crate::dto::{BlacklistRecord, ScoringRunRecord, UnstakeHint, UnstakeHintRecord};
+use crate::blacklist::{group_codes_by_vote_account, load_active_blacklist, load_blacklist_csv};
+use crate::dto::{
+    ScoringRunRecord, UnstakeHint, UnstakeHintHistoryRecord, UnstakeHintRecord, UnstakeHintsConfig,
+};
+use crate::scoring_engine::parse_version;
+use crate::utils::parse_scoring_config;
+use chrono::Utc;
 use rust_decimal::prelude::*;
 use std::collections::{HashMap, HashSet};
-use tokio_postgres::Client;
+use tokio_postgres::{Client, Row};
 
-const MAX_ALLOWED_COMMISSION: u8 = 10;
-const MIN_REQUIRED_CREDITS_PERFORMANCE: f64 = 0.5;
+impl UnstakeHintsConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.low_credits.enabled && self.low_credits.look_back_epochs == 0 {
+            anyhow::bail!("Low credits rule needs at least 1 look-back epoch");
+        }
+        if self.low_uptime.enabled && self.low_uptime.look_back_epochs == 0 {
+            anyhow::bail!("Low uptime rule needs at least 1 look-back epoch");
+        }
+        if self.outdated_version.enabled && self.outdated_version.min_version.is_empty() {
+            anyhow::bail!("Outdated version rule needs the minimal version");
+        }
 
-fn load_blacklist(blacklist_path: &String) -> anyhow::Result<HashMap<String, HashSet<String>>> {
-    let mut blacklist: Vec<BlacklistRecord> = Default::default();
-    let mut rdr = csv::Reader::from_path(blacklist_path)?;
-    for result in rdr.deserialize() {
-        blacklist.push(result?);
+        Ok(())
     }
+}
 
-    Ok(blacklist.into_iter().fold(
-        HashMap::new(),
-        |mut acc, BlacklistRecord { vote_account, code }| {
-            acc.entry(vote_account).or_default().insert(code);
+pub fn load_unstake_hints_config(config_path: &String) -> anyhow::Result<UnstakeHintsConfig> {
+    let config: UnstakeHintsConfig = serde_yaml::from_reader(std::fs::File::open(config_path)?)?;
+    config.validate()?;
 
-            acc
-        },
+    Ok(config)
+}
+
+pub fn load_blacklist(blacklist_path: &String) -> anyhow::Result<HashMap<String, HashSet<String>>> {
+    Ok(group_codes_by_vote_account(
+        load_blacklist_csv(blacklist_path)?
+            .into_iter()
+            .map(|record| (record.vote_account, record.code)),
     ))
 }
 
-async fn voter_max_commission_in_epoch(
+/// Epochs `from..=to` covering `look_back_epochs` epochs up to `epoch`
+fn look_back_range(epoch: u64, look_back_epochs: u64) -> (u64, u64) {
+    (
+        epoch.saturating_sub(look_back_epochs.saturating_sub(1)),
+        epoch,
+    )
+}
+
+async fn voter_max_commission_in_epochs(
     psql_client: &Client,
-    epoch: u64,
+    from_epoch: u64,
+    to_epoch: u64,
 ) -> anyhow::Result<HashMap<String, u8>> {
-    log::info!("Loading max commission per voter in epoch: {}", epoch);
+    log::info!(
+        "Loading max commission per voter in epochs: {} - {}",
+        from_epoch,
+        to_epoch
+    );
     let mut commissions: HashMap<_, _> = Default::default();
 
     let rows = psql_client
@@ -40,10 +70,10 @@ async fn voter_max_commission_in_epoch(
                         COALESCE(commission_max_observed, 0),
                         COALESCE(commission_advertised, 0)
                     )) commission
-                FROM validators LEFT JOIN commissions on validators.vote_account = commissions.vote_account
-                WHERE commissions.epoch = $1 and validators.epoch = $1
+                FROM validators LEFT JOIN commissions on validators.vote_account = commissions.vote_account AND validators.epoch = commissions.epoch
+                WHERE commissions.epoch BETWEEN $1 AND $2
                 GROUP BY validators.vote_account",
-            &[&Decimal::from(epoch)],
+            &[&Decimal::from(from_epoch), &Decimal::from(to_epoch)],
         )
         .await?;
 
@@ -80,81 +110,206 @@ async fn voters_with_marinade_stake_in_epoch(
         .collect())
 }
 
-async fn voters_credits_performance_in_epoch(
+async fn voters_credits_performance_in_epochs(
     psql_client: &Client,
-    epoch: u64,
+    from_epoch: u64,
+    to_epoch: u64,
+) -> anyhow::Result<HashMap<String, f64>> {
+    log::info!("Loading list of poor voters: {} - {}", from_epoch, to_epoch);
+    Ok(psql_client
+        .query(
+            "WITH stats AS (SELECT epoch, AVG(activated_stake * credits) / avg(activated_stake) as stake_weighted_avg_credits FROM validators WHERE epoch BETWEEN $1 AND $2 GROUP BY epoch)
+            SELECT
+                vote_account,
+                AVG(coalesce(credits / stake_weighted_avg_credits, 0))::double precision as credits_performance
+            FROM validators left join stats on validators.epoch = stats.epoch
+            WHERE validators.epoch BETWEEN $1 AND $2
+            GROUP BY vote_account",
+            &[&Decimal::from(from_epoch), &Decimal::from(to_epoch)],
+        )
+        .await?
+        .iter()
+        .map(|row| (row.get("vote_account"), row.get("credits_performance")))
+        .collect())
+}
+
+async fn voters_uptime_in_epochs(
+    psql_client: &Client,
+    from_epoch: u64,
+    to_epoch: u64,
 ) -> anyhow::Result<HashMap<String, f64>> {
+    log::info!("Loading uptimes of voters: {} - {}", from_epoch, to_epoch);
+    Ok(psql_client
+        .query(
+            "SELECT vote_account, AVG(uptime_pct)::double precision as uptime_pct
+            FROM validators
+            WHERE epoch BETWEEN $1 AND $2 AND uptime_pct IS NOT NULL
+            GROUP BY vote_account",
+            &[&Decimal::from(from_epoch), &Decimal::from(to_epoch)],
+        )
+        .await?
+        .iter()
+        .map(|row| (row.get("vote_account"), row.get("uptime_pct")))
+        .collect())
+}
+
+async fn voters_version_in_epoch(
+    psql_client: &Client,
+    epoch: u64,
+) -> anyhow::Result<HashMap<String, String>> {
+    log::info!("Loading versions of voters in epoch: {}", epoch);
+    Ok(psql_client
+        .query(
+            "SELECT vote_account, version
+            FROM validators
+            WHERE epoch = $1 AND version IS NOT NULL",
+            &[&Decimal::from(epoch)],
+        )
+        .await?
+        .iter()
+        .map(|row| (row.get("vote_account"), row.get("version")))
+        .collect())
+}
+
+async fn superminority_voters_in_epoch(
+    psql_client: &Client,
+    epoch: u64,
+) -> anyhow::Result<HashSet<String>> {
+    log::info!("Loading superminority voters in epoch: {}", epoch);
+    Ok(psql_client
+        .query(
+            "SELECT vote_account FROM validators WHERE epoch = $1 AND superminority",
+            &[&Decimal::from(epoch)],
+        )
+        .await?
+        .iter()
+        .map(|row| row.get("vote_account"))
+        .collect())
+}
+
+/// MEV snapshots are stored under the previous epoch, the newest stored epoch up to `epoch` is used
+async fn voter_max_mev_commission_in_epoch(
+    psql_client: &Client,
+    epoch: u64,
+) -> anyhow::Result<HashMap<String, i32>> {
     log::info!(
-        "Loading list of poor voters: {}",
+        "Loading max MEV commission per voter up to epoch: {}",
         epoch
     );
     Ok(psql_client
         .query(
-            "WITH stats AS (SELECT AVG(activated_stake * credits) / avg(activated_stake) as stake_weighted_avg_credits FROM validators WHERE epoch = $1)
-            SELECT
-                vote_account,
-                credits,
-                coalesce(credits / stake_weighted_avg_credits, 0)::double precision as credits_performance
-            FROM validators left join stats on 1 = 1
-            WHERE epoch = $1",
+            "SELECT vote_account, MAX(mev_commission) as mev_commission
+            FROM mev
+            WHERE epoch = (SELECT MAX(epoch) FROM mev WHERE epoch <= $1)
+            GROUP BY vote_account",
             &[&Decimal::from(epoch)],
         )
         .await?
         .iter()
-        .map(|row| (row.get("vote_account"), row.get("credits_performance")))
+        .map(|row| (row.get("vote_account"), row.get("mev_commission")))
         .collect())
 }
 
+fn add_hint(
+    hints: &mut HashMap<String, HashSet<UnstakeHint>>,
+    vote_account: String,
+    hint: UnstakeHint,
+) {
+    hints.entry(vote_account).or_default().insert(hint);
+}
+
 pub async fn load_unstake_hints(
     psql_client: &Client,
-    blacklist_path: &String,
     epoch: u64,
+    config: &UnstakeHintsConfig,
 ) -> anyhow::Result<Vec<UnstakeHintRecord>> {
     log::info!("Loading unstake hints in epoch: {}", epoch);
     let mut hints: HashMap<_, HashSet<_>> = Default::default();
 
     let marinade_staked_validators =
         voters_with_marinade_stake_in_epoch(psql_client, epoch).await?;
-    let commissions_in_this_epoch = voter_max_commission_in_epoch(psql_client, epoch).await?;
-    let commissions_in_previous_epoch = if epoch > 0 {
-        voter_max_commission_in_epoch(psql_client, epoch - 1).await?
-    } else {
-        Default::default()
-    };
-    let voters_credits_performance = voters_credits_performance_in_epoch(psql_client, epoch).await?;
-    let blacklist = load_blacklist(blacklist_path)?;
-
-    for (vote_account, commission) in commissions_in_this_epoch {
-        if commission > MAX_ALLOWED_COMMISSION {
-            hints
-                .entry(vote_account)
-                .or_default()
-                .insert(UnstakeHint::HighCommission);
+
+    let rule = &config.high_commission;
+    if rule.enabled {
+        for (vote_account, commission) in
+            voter_max_commission_in_epochs(psql_client, epoch, epoch).await?
+        {
+            if commission > rule.max_commission {
+                add_hint(&mut hints, vote_account, UnstakeHint::HighCommission);
+            }
+        }
+
+        if epoch > 0 && rule.look_back_epochs > 0 {
+            let (from_epoch, to_epoch) = look_back_range(epoch - 1, rule.look_back_epochs);
+            for (vote_account, commission) in
+                voter_max_commission_in_epochs(psql_client, from_epoch, to_epoch).await?
+            {
+                if commission > rule.max_commission {
+                    add_hint(
+                        &mut hints,
+                        vote_account,
+                        UnstakeHint::HighCommissionInPreviousEpoch,
+                    );
+                }
+            }
+        }
+    }
+
+    if config.blacklist.enabled {
+        for (vote_account, _) in load_active_blacklist(psql_client).await? {
+            add_hint(&mut hints, vote_account, UnstakeHint::Blacklist);
+        }
+    }
+
+    let rule = &config.low_credits;
+    if rule.enabled {
+        let (from_epoch, to_epoch) = look_back_range(epoch, rule.look_back_epochs);
+        for (vote_account, performance) in
+            voters_credits_performance_in_epochs(psql_client, from_epoch, to_epoch).await?
+        {
+            if performance < rule.min_credits_performance {
+                add_hint(&mut hints, vote_account, UnstakeHint::LowCredits);
+            }
+        }
+    }
+
+    // Uptime is known only for the closed epochs
+    let rule = &config.low_uptime;
+    if rule.enabled && epoch > 0 {
+        let (from_epoch, to_epoch) = look_back_range(epoch - 1, rule.look_back_epochs);
+        for (vote_account, uptime_pct) in
+            voters_uptime_in_epochs(psql_client, from_epoch, to_epoch).await?
+        {
+            if uptime_pct < rule.min_uptime_pct {
+                add_hint(&mut hints, vote_account, UnstakeHint::LowUptime);
+            }
         }
     }
 
-    for (vote_account, commission) in commissions_in_previous_epoch {
-        if commission > MAX_ALLOWED_COMMISSION {
-            hints
-                .entry(vote_account)
-                .or_default()
-                .insert(UnstakeHint::HighCommissionInPreviousEpoch);
+    let rule = &config.outdated_version;
+    if rule.enabled {
+        let min_version = parse_version(&rule.min_version);
+        for (vote_account, version) in voters_version_in_epoch(psql_client, epoch).await? {
+            if parse_version(&version) < min_version {
+                add_hint(&mut hints, vote_account, UnstakeHint::OutdatedVersion);
+            }
         }
     }
 
-    for (vote_account, _) in blacklist {
-        hints
-            .entry(vote_account)
-            .or_default()
-            .insert(UnstakeHint::Blacklist);
+    if config.superminority.enabled {
+        for vote_account in superminority_voters_in_epoch(psql_client, epoch).await? {
+            add_hint(&mut hints, vote_account, UnstakeHint::Superminority);
+        }
     }
 
-    for (vote_account, performance) in voters_credits_performance {
-        if performance < MIN_REQUIRED_CREDITS_PERFORMANCE {
-            hints
-                .entry(vote_account)
-                .or_default()
-                .insert(UnstakeHint::LowCredits);
+    let rule = &config.high_mev_commission;
+    if rule.enabled {
+        for (vote_account, mev_commission) in
+            voter_max_mev_commission_in_epoch(psql_client, epoch).await?
+        {
+            if mev_commission > rule.max_mev_commission_bps.into() {
+                add_hint(&mut hints, vote_account, UnstakeHint::HighMevCommission);
+            }
         }
     }
 
@@ -173,31 +328,167 @@ pub async fn load_unstake_hints(
         .collect())
 }
 
+pub async fn store_unstake_hints(
+    psql_client: &mut Client,
+    epoch: u64,
+    unstake_hints: &[UnstakeHintRecord],
+) -> anyhow::Result<u64> {
+    let epoch = Decimal::from(epoch);
+    let mut vote_accounts: Vec<&String> = Default::default();
+    let mut hints: Vec<&str> = Default::default();
+    let mut marinade_stakes: Vec<f64> = Default::default();
+    for record in unstake_hints {
+        for hint in record.hints.iter() {
+            vote_accounts.push(&record.vote_account);
+            hints.push(hint.code());
+            marinade_stakes.push(record.marinade_stake);
+        }
+    }
+
+    // The snapshot of the epoch is replaced at once, the history never sees it partially written
+    let transaction = psql_client.transaction().await?;
+    transaction
+        .execute("DELETE FROM unstake_hints WHERE epoch = $1", &[&epoch])
+        .await?;
+    let insertions = transaction
+        .execute(
+            "INSERT INTO unstake_hints (vote_account, epoch, hint, marinade_stake, created_at)
+            SELECT vote_account, $1, hint, marinade_stake, $5
+            FROM UNNEST($2::TEXT[], $3::TEXT[], $4::DOUBLE PRECISION[]) AS u(vote_account, hint, marinade_stake)",
+            &[&epoch, &vote_accounts, &hints, &marinade_stakes, &Utc::now()],
+        )
+        .await?;
+    transaction.commit().await?;
+
+    Ok(insertions)
+}
+
+pub async fn load_unstake_hints_history(
+    psql_client: &Client,
+    vote_account: Option<String>,
+) -> anyhow::Result<Vec<UnstakeHintHistoryRecord>> {
+    log::info!("Loading unstake hints history");
+    // Consecutive snapshots with the same hint are merged into a single record,
+    // epochs in which no snapshot was taken do not break the streak.
+    let rows = psql_client
+        .query(
+            "WITH snapshots AS (
+                SELECT epoch, DENSE_RANK() OVER (ORDER BY epoch) AS snapshot_index
+                FROM (SELECT DISTINCT epoch FROM unstake_hints) AS epochs
+            ),
+            streaks AS (
+                SELECT
+                    vote_account,
+                    hint,
+                    unstake_hints.epoch,
+                    snapshot_index - ROW_NUMBER() OVER (PARTITION BY vote_account, hint ORDER BY unstake_hints.epoch) AS streak
+                FROM unstake_hints
+                    INNER JOIN snapshots ON snapshots.epoch = unstake_hints.epoch
+                WHERE $1::text IS NULL OR vote_account = $1
+            )
+            SELECT
+                vote_account,
+                hint,
+                MIN(epoch) AS first_seen_epoch,
+                MAX(epoch) AS last_seen_epoch,
+                MAX(epoch) = (SELECT MAX(epoch) FROM snapshots) AS active
+            FROM streaks
+            GROUP BY vote_account, hint, streak
+            ORDER BY vote_account, hint, first_seen_epoch",
+            &[&vote_account],
+        )
+        .await?;
+
+    let mut records = Vec::with_capacity(rows.len());
+    for row in rows {
+        records.push(UnstakeHintHistoryRecord {
+            vote_account: row.get("vote_account"),
+            hint: UnstakeHint::from_code(row.get("hint"))?,
+            first_seen_epoch: row
+                .get::<_, Decimal>("first_seen_epoch")
+                .to_u64()
+                .unwrap_or_default(),
+            last_seen_epoch: row
+                .get::<_, Decimal>("last_seen_epoch")
+                .to_u64()
+                .unwrap_or_default(),
+            active: row.get("active"),
+        });
+    }
+
+    Ok(records)
+}
+
+const SCORING_RUN_COLUMNS: &str = "
+    scoring_run_id::numeric,
+    created_at,
+    epoch,
+    components,
+    component_weights,
+    ui_id,
+    config";
+
+fn scoring_run_from_row(scoring_run: &Row) -> anyhow::Result<ScoringRunRecord> {
+    Ok(ScoringRunRecord {
+        scoring_run_id: scoring_run.get("scoring_run_id"),
+        created_at: scoring_run.get("created_at"),
+        epoch: scoring_run.get("epoch"),
+        components: scoring_run.get("components"),
+        component_weights: scoring_run.get("component_weights"),
+        ui_id: scoring_run.get("ui_id"),
+        config: parse_scoring_config(scoring_run.get("config"))?,
+    })
+}
+
 pub async fn load_scoring_runs(psql_client: &Client) -> anyhow::Result<Vec<ScoringRunRecord>> {
     log::info!("Querying all scoring runs...");
-    Ok(psql_client
+    psql_client
         .query(
-            "
-            SELECT
-                scoring_run_id::numeric,
-                created_at,
-                epoch,
-                components,
-                component_weights,
-                ui_id
-            FROM scoring_runs
-            ORDER BY scoring_run_id DESC",
+            &format!(
+                "SELECT {} FROM scoring_runs ORDER BY scoring_run_id DESC",
+                SCORING_RUN_COLUMNS
+            ),
             &[],
         )
         .await?
-        .into_iter()
-        .map(|scoring_run| ScoringRunRecord {
-            scoring_run_id: scoring_run.get("scoring_run_id"),
-            created_at: scoring_run.get("created_at"),
-            epoch: scoring_run.get("epoch"),
-            components: scoring_run.get("components"),
-            component_weights: scoring_run.get("component_weights"),
-            ui_id: scoring_run.get("ui_id"),
-        })
-        .collect())
+        .iter()
+        .map(scoring_run_from_row)
+        .collect()
+}
+
+pub async fn load_scoring_runs_after(
+    psql_client: &Client,
+    scoring_run_id: i64,
+) -> anyhow::Result<Vec<ScoringRunRecord>> {
+    psql_client
+        .query(
+            &format!(
+                "SELECT {} FROM scoring_runs WHERE scoring_run_id > $1 ORDER BY scoring_run_id",
+                SCORING_RUN_COLUMNS
+            ),
+            &[&scoring_run_id],
+        )
+        .await?
+        .iter()
+        .map(scoring_run_from_row)
+        .collect()
+}
+
+pub async fn load_scoring_run(
+    psql_client: &Client,
+    scoring_run_id: i64,
+) -> anyhow::Result<Option<ScoringRunRecord>> {
+    log::info!("Querying scoring run: {}", scoring_run_id);
+    psql_client
+        .query_opt(
+            &format!(
+                "SELECT {} FROM scoring_runs WHERE scoring_run_id = $1",
+                SCORING_RUN_COLUMNS
+            ),
+            &[&scoring_run_id],
+        )
+        .await?
+        .as_ref()
+        .map(scoring_run_from_row)
+        .transpose()
 }
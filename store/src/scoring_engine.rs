@@ -0,0 +1,678 @@
+use crate::dto::{
+    ScoringConfig, ScoringEligibility, ScoringWeights, StakeControlSplit, StakeEligibility,
+    ValidatorAggregatedFlat, ValidatorScoreDiff, ValidatorScoreRecord, ValidatorScoringCsvRow,
+};
+use rust_decimal::prelude::*;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+pub const COMPONENT_ADJUSTED_CREDITS: &str = "COMMISSION_ADJUSTED_CREDITS";
+pub const COMPONENT_GRACE_SKIP_RATE: &str = "GRACE_SKIP_RATE";
+pub const COMPONENT_DC_CONCENTRATION: &str = "DC_CONCENTRATION";
+pub const COMPONENT_VOTE_LATENCY: &str = "VOTE_LATENCY";
+
+pub const SCORING_CONFIG_VERSION: u32 = 1;
+
+// Sanity limits of `scripts/scoring.R`
+const MIN_TOTAL_STAKE: f64 = 3e6;
+const MIN_MSOL_SOL: f64 = 900000.0;
+const MIN_SCORED_VALIDATORS: usize = 1000;
+
+impl ScoringConfig {
+    pub fn stake_control_algo(&self) -> f64 {
+        1.0 - self.stake_control.mnde - self.stake_control.msol
+    }
+
+    /// Components in the order expected by `store_scoring`
+    pub fn components(&self) -> Vec<&'static str> {
+        let mut components = vec![
+            COMPONENT_ADJUSTED_CREDITS,
+            COMPONENT_GRACE_SKIP_RATE,
+            COMPONENT_DC_CONCENTRATION,
+        ];
+        if self.weights.vote_latency > 0.0 {
+            components.push(COMPONENT_VOTE_LATENCY);
+        }
+
+        components
+    }
+
+    pub fn component_weights(&self) -> Vec<f64> {
+        let mut weights = vec![
+            self.weights.adjusted_credits,
+            self.weights.grace_skip_rate,
+            self.weights.dc_concentration,
+        ];
+        if self.weights.vote_latency > 0.0 {
+            weights.push(self.weights.vote_latency);
+        }
+
+        weights
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.version != SCORING_CONFIG_VERSION {
+            anyhow::bail!(
+                "Unsupported scoring config version: {} (expected {})",
+                self.version,
+                SCORING_CONFIG_VERSION
+            );
+        }
+        if self.weights.vote_latency < 0.0 {
+            anyhow::bail!("Weight of the vote latency must not be negative");
+        }
+        if self.component_weights().iter().sum::<f64>() <= 0.0 {
+            anyhow::bail!("Sum of the component weights must be positive");
+        }
+        if self.stake_control_algo() < 0.0 {
+            anyhow::bail!("Stake control splits for MNDE and mSOL must not exceed 1");
+        }
+        for eligibility in [&self.eligibility.mnde_stake, &self.eligibility.msol_stake] {
+            if eligibility.score_threshold_multiplier.is_none() {
+                anyhow::bail!("Score threshold multiplier is required for MNDE and mSOL stake");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn load_scoring_config(config_path: &String) -> anyhow::Result<ScoringConfig> {
+    let config: ScoringConfig = serde_yaml::from_reader(std::fs::File::open(config_path)?)?;
+    config.validate()?;
+
+    Ok(config)
+}
+
+/// Loads the config from the `params.env` file used by `scripts/scoring.R`
+pub fn load_scoring_config_from_env_file(params_path: &String) -> anyhow::Result<ScoringConfig> {
+    let content = std::fs::read_to_string(params_path)?;
+    let values: HashMap<_, _> = content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    let get = |key: &str| -> anyhow::Result<&String> {
+        values
+            .get(key)
+            .ok_or_else(|| anyhow::anyhow!("Missing scoring parameter: {}", key))
+    };
+    let get_f64 = |key: &str| -> anyhow::Result<f64> { Ok(get(key)?.parse()?) };
+    let get_u8 = |key: &str| -> anyhow::Result<u8> { Ok(get(key)?.parse()?) };
+
+    let config = ScoringConfig {
+        version: SCORING_CONFIG_VERSION,
+        total_stake: get_f64("TOTAL_STAKE")?,
+        marinade_validators_count: get("MARINADE_VALIDATORS_COUNT")?.parse()?,
+        weights: ScoringWeights {
+            adjusted_credits: get_f64("WEIGHT_ADJUSTED_CREDITS")?,
+            grace_skip_rate: get_f64("WEIGHT_GRACE_SKIP_RATE")?,
+            dc_concentration: get_f64("WEIGHT_DC_CONCENTRATION")?,
+            vote_latency: match values.get("WEIGHT_VOTE_LATENCY") {
+                Some(weight) => weight.parse()?,
+                None => 0.0,
+            },
+        },
+        eligibility: ScoringEligibility {
+            min_version: get("ELIGIBILITY_MIN_VERSION")?.clone(),
+            algo_stake: StakeEligibility {
+                max_commission: get_u8("ELIGIBILITY_ALGO_STAKE_MAX_COMMISSION")?,
+                min_stake: get_f64("ELIGIBILITY_ALGO_STAKE_MIN_STAKE")?,
+                score_threshold_multiplier: None,
+            },
+            mnde_stake: StakeEligibility {
+                max_commission: get_u8("ELIGIBILITY_MNDE_STAKE_MAX_COMMISSION")?,
+                min_stake: get_f64("ELIGIBILITY_MNDE_STAKE_MIN_STAKE")?,
+                score_threshold_multiplier: Some(get_f64(
+                    "ELIGIBILITY_MNDE_SCORE_THRESHOLD_MULTIPLIER",
+                )?),
+            },
+            msol_stake: StakeEligibility {
+                max_commission: get_u8("ELIGIBILITY_MSOL_STAKE_MAX_COMMISSION")?,
+                min_stake: get_f64("ELIGIBILITY_MSOL_STAKE_MIN_STAKE")?,
+                score_threshold_multiplier: Some(get_f64(
+                    "ELIGIBILITY_MSOL_SCORE_THRESHOLD_MULTIPLIER",
+                )?),
+            },
+        },
+        stake_control: StakeControlSplit {
+            mnde: get_f64("STAKE_CONTROL_MNDE")?,
+            msol: get_f64("STAKE_CONTROL_MSOL")?,
+        },
+        mnde_validator_cap: get_f64("MNDE_VALIDATOR_CAP")?,
+    };
+    config.validate()?;
+
+    Ok(config)
+}
+
+pub fn load_msol_votes(msol_votes_path: &String) -> anyhow::Result<HashMap<String, f64>> {
+    let mut msol_votes: HashMap<String, f64> = Default::default();
+    let mut rdr = csv::Reader::from_path(msol_votes_path)?;
+    for result in rdr.deserialize() {
+        let (vote_account, votes): (String, f64) = result?;
+        msol_votes.insert(vote_account, votes);
+    }
+
+    Ok(msol_votes)
+}
+
+#[derive(Serialize, Debug, Clone, Default, utoipa::ToSchema)]
+pub struct StakeControl {
+    pub msol_sol: f64,
+    pub msol_unused_sol: f64,
+    pub mnde_sol: f64,
+    pub mnde_overflow_sol: f64,
+    pub algo_sol: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScoringResult {
+    pub scores: Vec<ValidatorScoringCsvRow>,
+    pub stake_control: StakeControl,
+}
+
+#[derive(Debug, Default)]
+struct ScoredValidator {
+    vote_account: String,
+    max_commission: u8,
+    minimum_stake: f64,
+    version_ok: bool,
+    blacklisted: bool,
+    mnde_votes: u64,
+    msol_votes: f64,
+    avg_dc_concentration: f64,
+    avg_grace_skip_rate: f64,
+    avg_adjusted_credits: f64,
+    avg_vote_latency: Option<f64>,
+    normalized_dc_concentration: f64,
+    normalized_grace_skip_rate: f64,
+    normalized_adjusted_credits: f64,
+    normalized_vote_latency: f64,
+    rank_dc_concentration: usize,
+    rank_grace_skip_rate: usize,
+    rank_adjusted_credits: usize,
+    rank_vote_latency: usize,
+    score: f64,
+    rank: usize,
+    ui_hints: Vec<String>,
+    eligible_stake_algo: bool,
+    eligible_stake_mnde: bool,
+    eligible_stake_msol: bool,
+    in_algo_stake_set: bool,
+    msol_power: f64,
+    mnde_power: f64,
+    target_stake_algo: f64,
+    target_stake_mnde: f64,
+    target_stake_msol: f64,
+}
+
+impl ScoredValidator {
+    fn into_csv_row(self) -> ValidatorScoringCsvRow {
+        ValidatorScoringCsvRow {
+            vote_account: self.vote_account,
+            score: self.score,
+            rank: self.rank as i32,
+            mnde_votes: self.mnde_votes.into(),
+            ui_hints: self.ui_hints.join(","),
+            eligible_stake_algo: self.eligible_stake_algo,
+            eligible_stake_mnde: self.eligible_stake_mnde,
+            eligible_stake_msol: self.eligible_stake_msol,
+            normalized_dc_concentration: self.normalized_dc_concentration,
+            normalized_grace_skip_rate: self.normalized_grace_skip_rate,
+            normalized_adjusted_credits: self.normalized_adjusted_credits,
+            avg_dc_concentration: self.avg_dc_concentration,
+            avg_grace_skip_rate: self.avg_grace_skip_rate,
+            avg_adjusted_credits: self.avg_adjusted_credits,
+            rank_dc_concentration: self.rank_dc_concentration as i32,
+            rank_grace_skip_rate: self.rank_grace_skip_rate as i32,
+            rank_adjusted_credits: self.rank_adjusted_credits as i32,
+            normalized_vote_latency: self.normalized_vote_latency,
+            avg_vote_latency: self.avg_vote_latency,
+            rank_vote_latency: self.rank_vote_latency as i32,
+            target_stake_algo: Decimal::from_f64(self.target_stake_algo).unwrap_or_default(),
+            target_stake_mnde: Decimal::from_f64(self.target_stake_mnde).unwrap_or_default(),
+            target_stake_msol: Decimal::from_f64(self.target_stake_msol).unwrap_or_default(),
+        }
+    }
+}
+
+fn normalize(values: &[f64]) -> Vec<f64> {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|value| {
+            if range > 0.0 {
+                (value - min) / range
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Ranks in descending order where ties share the best rank, same as `rank(-x, ties.method="min")` in R
+fn rank_desc(values: &[f64]) -> Vec<usize> {
+    values
+        .iter()
+        .map(|value| 1 + values.iter().filter(|other| *other > value).count())
+        .collect()
+}
+
+pub(crate) fn parse_version(version: &str) -> (u64, u64, u64) {
+    let mut parts = version
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse::<u64>().unwrap_or(0));
+
+    (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}
+
+/// Replicates `scripts/scoring.R`: scores are min-max normalized components combined by weights,
+/// algo stake goes to the best eligible validators and MNDE/mSOL stake follows the votes.
+pub fn compute_scores(
+    validators: &[ValidatorAggregatedFlat],
+    blacklist: &HashMap<String, HashSet<String>>,
+    msol_votes: &HashMap<String, f64>,
+    config: &ScoringConfig,
+) -> ScoringResult {
+    let normalized_dc_concentration = normalize(
+        &validators
+            .iter()
+            .map(|v| 1.0 - v.avg_dc_concentration)
+            .collect::<Vec<_>>(),
+    );
+    let normalized_grace_skip_rate = normalize(
+        &validators
+            .iter()
+            .map(|v| 1.0 - v.avg_grace_skip_rate)
+            .collect::<Vec<_>>(),
+    );
+    let normalized_adjusted_credits = normalize(
+        &validators
+            .iter()
+            .map(|v| v.avg_adjusted_credits)
+            .collect::<Vec<_>>(),
+    );
+    // Validators without vote latency get the worst latency of the others
+    let max_vote_latency = validators
+        .iter()
+        .filter_map(|v| v.avg_vote_latency)
+        .fold(0.0, f64::max);
+    let normalized_vote_latency = normalize(
+        &validators
+            .iter()
+            .map(|v| -v.avg_vote_latency.unwrap_or(max_vote_latency))
+            .collect::<Vec<_>>(),
+    );
+    let rank_dc_concentration = rank_desc(&normalized_dc_concentration);
+    let rank_grace_skip_rate = rank_desc(&normalized_grace_skip_rate);
+    let rank_adjusted_credits = rank_desc(&normalized_adjusted_credits);
+    let rank_vote_latency = rank_desc(&normalized_vote_latency);
+
+    let weights_total = config.weights.adjusted_credits
+        + config.weights.grace_skip_rate
+        + config.weights.dc_concentration
+        + config.weights.vote_latency;
+    let min_version = parse_version(&config.eligibility.min_version);
+
+    let mut scored: Vec<ScoredValidator> = validators
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let mut ui_hints: Vec<String> = Default::default();
+            if let Some(codes) = blacklist.get(&v.vote_account) {
+                let mut codes: Vec<_> = codes.iter().cloned().collect();
+                codes.sort();
+                ui_hints.extend(codes);
+            }
+
+            ScoredValidator {
+                vote_account: v.vote_account.clone(),
+                max_commission: v.max_commission,
+                minimum_stake: v.minimum_stake,
+                version_ok: parse_version(&v.version) >= min_version,
+                blacklisted: !ui_hints.is_empty(),
+                mnde_votes: v.mnde_votes,
+                msol_votes: msol_votes.get(&v.vote_account).cloned().unwrap_or(0.0),
+                avg_dc_concentration: v.avg_dc_concentration,
+                avg_grace_skip_rate: v.avg_grace_skip_rate,
+                avg_adjusted_credits: v.avg_adjusted_credits,
+                avg_vote_latency: v.avg_vote_latency,
+                normalized_dc_concentration: normalized_dc_concentration[i],
+                normalized_grace_skip_rate: normalized_grace_skip_rate[i],
+                normalized_adjusted_credits: normalized_adjusted_credits[i],
+                normalized_vote_latency: normalized_vote_latency[i],
+                rank_dc_concentration: rank_dc_concentration[i],
+                rank_grace_skip_rate: rank_grace_skip_rate[i],
+                rank_adjusted_credits: rank_adjusted_credits[i],
+                rank_vote_latency: rank_vote_latency[i],
+                score: (normalized_dc_concentration[i] * config.weights.dc_concentration
+                    + normalized_grace_skip_rate[i] * config.weights.grace_skip_rate
+                    + normalized_adjusted_credits[i] * config.weights.adjusted_credits
+                    + normalized_vote_latency[i] * config.weights.vote_latency)
+                    / weights_total,
+                ui_hints,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    for v in scored.iter_mut() {
+        v.eligible_stake_algo = !v.blacklisted
+            && v.max_commission <= config.eligibility.algo_stake.max_commission
+            && v.minimum_stake >= config.eligibility.algo_stake.min_stake
+            && v.version_ok;
+
+        if v.max_commission > config.eligibility.algo_stake.max_commission {
+            v.ui_hints
+                .push("NOT_ELIGIBLE_ALGO_STAKE_MAX_COMMISSION_OVER_10".into());
+        }
+        if v.minimum_stake < config.eligibility.algo_stake.min_stake {
+            v.ui_hints
+                .push("NOT_ELIGIBLE_ALGO_STAKE_MIN_STAKE_BELOW_1000".into());
+        }
+        if !v.version_ok {
+            v.ui_hints.push("NOT_ELIGIBLE_VERSION_TOO_LOW".into());
+        }
+    }
+
+    let ranks = rank_desc(&scored.iter().map(|v| v.score).collect::<Vec<_>>());
+    for (v, rank) in scored.iter_mut().zip(ranks) {
+        v.rank = rank;
+    }
+    scored.sort_by_key(|v| v.rank);
+
+    let min_score_in_algo_set = scored
+        .iter()
+        .filter(|v| v.eligible_stake_algo)
+        .take(config.marinade_validators_count)
+        .map(|v| v.score)
+        .fold(f64::INFINITY, f64::min);
+    let msol_score_threshold = min_score_in_algo_set
+        * config
+            .eligibility
+            .msol_stake
+            .score_threshold_multiplier
+            .unwrap_or_default();
+    let mnde_score_threshold = min_score_in_algo_set
+        * config
+            .eligibility
+            .mnde_stake
+            .score_threshold_multiplier
+            .unwrap_or_default();
+
+    for v in scored.iter_mut() {
+        v.in_algo_stake_set = v.eligible_stake_algo && v.score >= min_score_in_algo_set;
+
+        v.eligible_stake_msol = !v.blacklisted
+            && v.max_commission <= config.eligibility.msol_stake.max_commission
+            && v.minimum_stake >= config.eligibility.msol_stake.min_stake
+            && v.score >= msol_score_threshold
+            && v.version_ok;
+
+        if v.max_commission > config.eligibility.msol_stake.max_commission {
+            v.ui_hints
+                .push("NOT_ELIGIBLE_MSOL_STAKE_MAX_COMMISSION_OVER_10".into());
+        }
+        if v.minimum_stake < config.eligibility.msol_stake.min_stake {
+            v.ui_hints
+                .push("NOT_ELIGIBLE_MSOL_STAKE_MIN_STAKE_BELOW_100".into());
+        }
+        if v.score < msol_score_threshold {
+            v.ui_hints
+                .push("NOT_ELIGIBLE_MSOL_STAKE_SCORE_TOO_LOW".into());
+        }
+    }
+
+    // R rounds half to even
+    let msol_valid_votes: Vec<f64> = scored
+        .iter()
+        .map(|v| (v.msol_votes * v.eligible_stake_msol as u8 as f64).round_ties_even())
+        .collect();
+    let msol_valid_votes_total: f64 = msol_valid_votes.iter().sum();
+    if msol_valid_votes_total > 0.0 {
+        for (v, votes) in scored.iter_mut().zip(msol_valid_votes) {
+            v.msol_power = votes / msol_valid_votes_total;
+        }
+    }
+
+    for v in scored.iter_mut() {
+        v.eligible_stake_mnde = !v.blacklisted
+            && v.max_commission <= config.eligibility.mnde_stake.max_commission
+            && v.minimum_stake >= config.eligibility.mnde_stake.min_stake
+            && v.score >= mnde_score_threshold
+            && v.version_ok;
+
+        if v.max_commission > config.eligibility.mnde_stake.max_commission {
+            v.ui_hints
+                .push("NOT_ELIGIBLE_MNDE_STAKE_MAX_COMMISSION_OVER_10".into());
+        }
+        if v.minimum_stake < config.eligibility.mnde_stake.min_stake {
+            v.ui_hints
+                .push("NOT_ELIGIBLE_MNDE_STAKE_MIN_STAKE_BELOW_100".into());
+        }
+        if v.score < mnde_score_threshold {
+            v.ui_hints
+                .push("NOT_ELIGIBLE_MNDE_STAKE_SCORE_TOO_LOW".into());
+        }
+    }
+
+    let mnde_valid_votes: Vec<f64> = scored
+        .iter()
+        .map(|v| (v.mnde_votes as f64 * v.eligible_stake_mnde as u8 as f64 / 1e9).round_ties_even())
+        .collect();
+    let mnde_valid_votes_total: f64 = mnde_valid_votes.iter().sum();
+    let mnde_power_cap = (mnde_valid_votes_total * config.mnde_validator_cap).round_ties_even();
+    for (v, votes) in scored.iter_mut().zip(mnde_valid_votes) {
+        v.mnde_power = votes.min(mnde_power_cap);
+    }
+    let mut mnde_overflow =
+        mnde_valid_votes_total - scored.iter().map(|v| v.mnde_power).sum::<f64>();
+
+    // Distribute the overflow from the capping, starting with the most voted validators
+    scored.sort_by(|a, b| b.mnde_power.total_cmp(&a.mnde_power));
+    for i in 0..scored.len() {
+        let moving_weights: f64 = scored[i..].iter().map(|v| v.mnde_power).sum();
+        if moving_weights == 0.0 {
+            break;
+        }
+        let mnde_power_increase =
+            (mnde_overflow * scored[i].mnde_power / moving_weights).round_ties_even();
+        let mnde_power_increase_capped =
+            mnde_power_increase.min(mnde_power_cap - scored[i].mnde_power);
+        scored[i].mnde_power += mnde_power_increase_capped;
+        mnde_overflow -= mnde_power_increase_capped;
+    }
+
+    let mnde_power_total: f64 = scored.iter().map(|v| v.mnde_power).sum();
+    let mnde_overflow_power = if mnde_power_total > 0.0 {
+        let total_mnde_power = mnde_power_total + mnde_overflow;
+        for v in scored.iter_mut() {
+            v.mnde_power /= total_mnde_power;
+        }
+        mnde_overflow / total_mnde_power
+    } else {
+        1.0
+    };
+
+    let msol_votes_used = msol_valid_votes_total > 0.0;
+    let mnde_sol = config.total_stake * config.stake_control.mnde * (1.0 - mnde_overflow_power);
+    let mnde_overflow_sol = mnde_overflow_power * config.total_stake * config.stake_control.mnde;
+    let msol_sol = if msol_votes_used {
+        config.total_stake * config.stake_control.msol
+    } else {
+        0.0
+    };
+    let msol_unused_sol = if msol_votes_used {
+        0.0
+    } else {
+        config.total_stake * config.stake_control.msol
+    };
+    let stake_control = StakeControl {
+        msol_sol,
+        msol_unused_sol,
+        mnde_sol,
+        mnde_overflow_sol,
+        algo_sol: config.total_stake * config.stake_control_algo()
+            + mnde_overflow_sol
+            + msol_unused_sol,
+    };
+
+    let algo_score_total: f64 = scored
+        .iter()
+        .filter(|v| v.in_algo_stake_set)
+        .map(|v| v.score)
+        .sum();
+    for v in scored.iter_mut() {
+        v.target_stake_mnde = (v.mnde_power * stake_control.mnde_sol).round_ties_even();
+        v.target_stake_msol = (v.msol_power * stake_control.msol_sol).round_ties_even();
+        if v.in_algo_stake_set && algo_score_total > 0.0 {
+            v.target_stake_algo =
+                (v.score / algo_score_total * stake_control.algo_sol).round_ties_even();
+        }
+    }
+
+    scored.sort_by_key(|v| v.rank);
+
+    ScoringResult {
+        scores: scored.into_iter().map(|v| v.into_csv_row()).collect(),
+        stake_control,
+    }
+}
+
+/// Sanity checks of `scripts/scoring.R`, a result failing them must not be stored
+pub fn check_scoring_result(config: &ScoringConfig, result: &ScoringResult) -> anyhow::Result<()> {
+    if config.total_stake <= MIN_TOTAL_STAKE {
+        anyhow::bail!(
+            "Total stake {} is not above {}",
+            config.total_stake,
+            MIN_TOTAL_STAKE
+        );
+    }
+    if result.stake_control.msol_sol <= MIN_MSOL_SOL {
+        anyhow::bail!(
+            "mSOL stake {} is not above {}",
+            result.stake_control.msol_sol,
+            MIN_MSOL_SOL
+        );
+    }
+    if result.scores.len() <= MIN_SCORED_VALIDATORS {
+        anyhow::bail!(
+            "Scored validators {} are not above {}",
+            result.scores.len(),
+            MIN_SCORED_VALIDATORS
+        );
+    }
+    let algo_staked_validators = result
+        .scores
+        .iter()
+        .filter(|row| !row.target_stake_algo.is_zero())
+        .count();
+    if algo_staked_validators != config.marinade_validators_count {
+        anyhow::bail!(
+            "Validators receiving algo stake {} do not match the expected {}",
+            algo_staked_validators,
+            config.marinade_validators_count
+        );
+    }
+
+    Ok(())
+}
+
+fn total_target_stake(score: &ValidatorScoreRecord) -> u64 {
+    score.target_stake_algo + score.target_stake_mnde + score.target_stake_msol
+}
+
+fn component_score(
+    score: Option<&ValidatorScoreRecord>,
+    components: &[String],
+    component: &String,
+) -> Option<f64> {
+    let index = components.iter().position(|c| c == component)?;
+    score?.component_scores.get(index).cloned()
+}
+
+/// Compares two sets of scores per validator, component score deltas follow the order of `components_after`.
+/// Only validators whose rank, score, eligibility or target stake differ are listed,
+/// the biggest target stake changes come first.
+pub fn diff_scores(
+    before: &HashMap<String, ValidatorScoreRecord>,
+    components_before: &[String],
+    after: &HashMap<String, ValidatorScoreRecord>,
+    components_after: &[String],
+) -> Vec<ValidatorScoreDiff> {
+    let vote_accounts: HashSet<_> = before.keys().chain(after.keys()).collect();
+
+    let mut diff: Vec<_> = vote_accounts
+        .into_iter()
+        .map(|vote_account| {
+            let before = before.get(vote_account);
+            let after = after.get(vote_account);
+
+            ValidatorScoreDiff {
+                vote_account: vote_account.clone(),
+                rank_before: before.map(|s| s.rank),
+                rank_after: after.map(|s| s.rank),
+                rank_change: before.zip(after).map(|(b, a)| b.rank - a.rank),
+                score_before: before.map(|s| s.score),
+                score_after: after.map(|s| s.score),
+                score_delta: before.zip(after).map(|(b, a)| a.score - b.score),
+                component_score_deltas: components_after
+                    .iter()
+                    .map(|component| {
+                        let score_before = component_score(before, components_before, component)?;
+                        let score_after = component_score(after, components_after, component)?;
+                        Some(score_after - score_before)
+                    })
+                    .collect(),
+                eligible_stake_algo_before: before.map(|s| s.eligible_stake_algo),
+                eligible_stake_algo_after: after.map(|s| s.eligible_stake_algo),
+                eligible_stake_mnde_before: before.map(|s| s.eligible_stake_mnde),
+                eligible_stake_mnde_after: after.map(|s| s.eligible_stake_mnde),
+                eligible_stake_msol_before: before.map(|s| s.eligible_stake_msol),
+                eligible_stake_msol_after: after.map(|s| s.eligible_stake_msol),
+                target_stake_algo_before: before.map(|s| s.target_stake_algo).unwrap_or(0),
+                target_stake_algo_after: after.map(|s| s.target_stake_algo).unwrap_or(0),
+                target_stake_mnde_before: before.map(|s| s.target_stake_mnde).unwrap_or(0),
+                target_stake_mnde_after: after.map(|s| s.target_stake_mnde).unwrap_or(0),
+                target_stake_msol_before: before.map(|s| s.target_stake_msol).unwrap_or(0),
+                target_stake_msol_after: after.map(|s| s.target_stake_msol).unwrap_or(0),
+                target_stake_before: before.map(total_target_stake).unwrap_or(0),
+                target_stake_after: after.map(total_target_stake).unwrap_or(0),
+            }
+        })
+        .filter(|diff| {
+            diff.rank_before != diff.rank_after
+                || diff.score_before != diff.score_after
+                || diff.eligible_stake_algo_before != diff.eligible_stake_algo_after
+                || diff.eligible_stake_mnde_before != diff.eligible_stake_mnde_after
+                || diff.eligible_stake_msol_before != diff.eligible_stake_msol_after
+                || diff.target_stake_algo_before != diff.target_stake_algo_after
+                || diff.target_stake_mnde_before != diff.target_stake_mnde_after
+                || diff.target_stake_msol_before != diff.target_stake_msol_after
+        })
+        .collect();
+
+    diff.sort_by(|a, b| {
+        let change_a = a.target_stake_before.abs_diff(a.target_stake_after);
+        let change_b = b.target_stake_before.abs_diff(b.target_stake_after);
+        change_b
+            .cmp(&change_a)
+            .then_with(|| a.vote_account.cmp(&b.vote_account))
+    });
+
+    diff
+}
@@ -0,0 +1,169 @@
+use log::info;
+use store::blacklist::load_active_blacklist;
+use store::dto::{ScoringConfig, ScoringInputs};
+use store::scoring::load_blacklist;
+use store::scoring_engine::{
+    check_scoring_result, compute_scores, load_msol_votes, load_scoring_config,
+    load_scoring_config_from_env_file,
+};
+use store::utils::{load_validators_aggregated_flat, parse_scoring_config, store_scoring};
+use structopt::StructOpt;
+use tokio_postgres::Client;
+
+#[derive(Debug, StructOpt)]
+pub struct ScoringRunOptions {
+    #[structopt(
+        long = "epoch",
+        required_unless = "from-scoring-run-id",
+        help = "Last epoch of the data used for the scoring"
+    )]
+    epoch: Option<u64>,
+
+    #[structopt(long = "epochs", default_value = "10")]
+    epochs: u64,
+
+    #[structopt(long = "ui-id")]
+    ui_id: String,
+
+    #[structopt(
+        long = "config-file",
+        required_unless_one = &["params-path", "from-scoring-run-id"]
+    )]
+    config_path: Option<String>,
+
+    #[structopt(
+        long = "params-file",
+        conflicts_with = "config-path",
+        help = "Legacy params.env used by the R scoring"
+    )]
+    params_path: Option<String>,
+
+    #[structopt(
+        long = "blacklist-path",
+        help = "Use the blacklist CSV instead of the blacklist stored in DB"
+    )]
+    blacklist_path: Option<String>,
+
+    #[structopt(long = "msol-votes-file")]
+    msol_votes_path: Option<String>,
+
+    #[structopt(
+        long = "from-scoring-run",
+        conflicts_with_all = &["epoch", "config-path", "params-path", "blacklist-path", "msol-votes-path"],
+        help = "Reproduce the stored scoring run with its config and inputs"
+    )]
+    from_scoring_run_id: Option<i64>,
+
+    #[structopt(long = "output-csv", help = "Write the scores also to a CSV file")]
+    output_csv_path: Option<String>,
+
+    #[structopt(long = "dry-run", help = "Do not store the scoring run")]
+    dry_run: bool,
+}
+
+async fn load_scoring_run_inputs(
+    psql_client: &Client,
+    scoring_run_id: i64,
+) -> anyhow::Result<(ScoringConfig, ScoringInputs)> {
+    info!("Loading inputs of the scoring run: {}", scoring_run_id);
+    let row = psql_client
+        .query_opt(
+            "SELECT config, inputs FROM scoring_runs WHERE scoring_run_id = $1",
+            &[&scoring_run_id],
+        )
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Scoring run {} not found!", scoring_run_id))?;
+
+    let config = parse_scoring_config(row.get("config"))?
+        .ok_or_else(|| anyhow::anyhow!("Scoring run {} has no config!", scoring_run_id))?;
+    let inputs: ScoringInputs = match row.get::<_, Option<String>>("inputs") {
+        Some(inputs) => serde_yaml::from_str(&inputs)?,
+        None => anyhow::bail!("Scoring run {} has no inputs!", scoring_run_id),
+    };
+
+    Ok((config, inputs))
+}
+
+async fn load_inputs(
+    options: &ScoringRunOptions,
+    psql_client: &Client,
+) -> anyhow::Result<(ScoringConfig, ScoringInputs)> {
+    let config = match (&options.config_path, &options.params_path) {
+        (Some(path), _) => load_scoring_config(path)?,
+        (None, Some(path)) => load_scoring_config_from_env_file(path)?,
+        (None, None) => anyhow::bail!("Scoring config is missing!"),
+    };
+    let blacklist = match &options.blacklist_path {
+        Some(path) => load_blacklist(path)?,
+        None => load_active_blacklist(psql_client).await?,
+    };
+    let msol_votes = match &options.msol_votes_path {
+        Some(path) => load_msol_votes(path)?,
+        None => Default::default(),
+    };
+
+    Ok((
+        config,
+        ScoringInputs {
+            epoch: options
+                .epoch
+                .ok_or_else(|| anyhow::anyhow!("Epoch is missing!"))?,
+            epochs: options.epochs,
+            blacklist,
+            msol_votes,
+        },
+    ))
+}
+
+pub async fn scoring_run(
+    options: ScoringRunOptions,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    info!("Running the scoring...");
+
+    let (config, inputs) = match options.from_scoring_run_id {
+        Some(scoring_run_id) => load_scoring_run_inputs(psql_client, scoring_run_id).await?,
+        None => load_inputs(&options, psql_client).await?,
+    };
+    let validators =
+        load_validators_aggregated_flat(psql_client, inputs.epoch, inputs.epochs).await?;
+    info!("Loaded {} validators", validators.len());
+
+    let result = compute_scores(&validators, &inputs.blacklist, &inputs.msol_votes, &config);
+    info!("Stake control: {:?}", result.stake_control);
+    info!(
+        "Validators receiving algo stake: {}",
+        result
+            .scores
+            .iter()
+            .filter(|row| !row.target_stake_algo.is_zero())
+            .count()
+    );
+    check_scoring_result(&config, &result)?;
+
+    if let Some(path) = &options.output_csv_path {
+        let mut writer = csv::Writer::from_path(path)?;
+        for row in result.scores.iter() {
+            writer.serialize(row)?;
+        }
+        writer.flush()?;
+        info!("Scores written to: {}", path);
+    }
+
+    if options.dry_run {
+        info!("Dry run, the scoring run is not stored");
+        return Ok(());
+    }
+
+    store_scoring(
+        psql_client,
+        inputs.epoch.try_into()?,
+        options.ui_id,
+        config.components(),
+        config.component_weights(),
+        Some(&config),
+        Some(&inputs),
+        result.scores,
+    )
+    .await
+}
@@ -0,0 +1,41 @@
+use log::info;
+use store::scoring::{load_unstake_hints, load_unstake_hints_config, store_unstake_hints};
+use structopt::StructOpt;
+use tokio_postgres::Client;
+
+#[derive(Debug, StructOpt)]
+pub struct StoreUnstakeHintsOptions {
+    #[structopt(long = "epoch")]
+    epoch: u64,
+
+    #[structopt(
+        long = "config-file",
+        help = "Unstake hint rules, defaults are used if missing"
+    )]
+    config_path: Option<String>,
+}
+
+pub async fn store_unstake_hints_snapshot(
+    options: StoreUnstakeHintsOptions,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    info!("Storing unstake hints snapshot...");
+
+    let config = match &options.config_path {
+        Some(path) => load_unstake_hints_config(path)?,
+        None => Default::default(),
+    };
+    let unstake_hints = load_unstake_hints(psql_client, options.epoch, &config).await?;
+    info!(
+        "Loaded unstake hints for {} validators",
+        unstake_hints.len()
+    );
+
+    let insertions = store_unstake_hints(psql_client, options.epoch, &unstake_hints).await?;
+    info!(
+        "Stored {} unstake hints for epoch {}",
+        insertions, options.epoch
+    );
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
 use crate::utils::*;
 use chrono::{DateTime, Duration, Utc};
-use collect::validators_performance::ValidatorsPerformanceSnapshot;
+use collect::validators_performance::{ValidatorPerformance, ValidatorsPerformanceSnapshot};
 use log::{debug, info, warn};
 use rust_decimal::prelude::*;
 use serde_yaml;
 use std::collections::{HashMap, HashSet};
+use store::dto::{NotificationsConfig, ValidatorEvent, ValidatorEventType};
+use store::notifications::{load_notifications_config, notify};
 use structopt::StructOpt;
 use tokio_postgres::types::ToSql;
 use tokio_postgres::Client;
@@ -13,10 +15,21 @@ use tokio_postgres::Client;
 pub struct StoreUptimeOptions {
     #[structopt(long = "snapshot-file")]
     snapshot_path: String,
+
+    #[structopt(
+        long = "notifications-config",
+        help = "Validator events are sent to the sinks configured in this file"
+    )]
+    notifications_config_path: Option<String>,
 }
 
 static UP: &str = "UP";
 static DOWN: &str = "DOWN";
+static UNKNOWN: &str = "UNKNOWN";
+
+/// Same as the default delinquent slot distance of the RPC
+const DELINQUENT_SLOT_DISTANCE: u64 = 128;
+const DEFAULT_CHUNK_SIZE: usize = 500;
 
 fn status_from_delinquency(delinquent: bool) -> &'static str {
     if delinquent {
@@ -26,14 +39,68 @@ fn status_from_delinquency(delinquent: bool) -> &'static str {
     }
 }
 
+struct PreviousSample {
+    slot: u64,
+    credits: u64,
+}
+
+/// DOWN when the last vote is too far behind the snapshot slot, or when no credits were earned since
+/// the previous sample of the epoch although the cluster moved on by more than the delinquent slot distance.
+/// Snapshots without the slot fall back to the delinquency reported by the RPC.
+fn status_from_sample(
+    snapshot: &ValidatorsPerformanceSnapshot,
+    validator: &ValidatorPerformance,
+    previous_sample: Option<&PreviousSample>,
+) -> &'static str {
+    if snapshot.slot == 0 {
+        return status_from_delinquency(validator.delinquent);
+    }
+    if snapshot.slot.saturating_sub(validator.last_vote) > DELINQUENT_SLOT_DISTANCE {
+        return DOWN;
+    }
+    if let Some(previous_sample) = previous_sample {
+        if validator.credits <= previous_sample.credits
+            && snapshot.slot.saturating_sub(previous_sample.slot) > DELINQUENT_SLOT_DISTANCE
+        {
+            return DOWN;
+        }
+    }
+
+    UP
+}
+
+struct UptimeSample {
+    vote_account: String,
+    status: &'static str,
+    slot: Decimal,
+    last_vote: Decimal,
+    root_slot: Decimal,
+    vote_lag: Decimal,
+    root_lag: Decimal,
+    credits: Decimal,
+}
+
 pub async fn store_uptime(
     options: StoreUptimeOptions,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    let notifications_config = match &options.notifications_config_path {
+        Some(path) => load_notifications_config(path)?,
+        None => Default::default(),
+    };
+    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
+    let snapshot: ValidatorsPerformanceSnapshot = serde_yaml::from_reader(snapshot_file)?;
+
+    store_uptime_snapshot(&snapshot, psql_client, &notifications_config).await
+}
+
+pub async fn store_uptime_snapshot(
+    snapshot: &ValidatorsPerformanceSnapshot,
     mut psql_client: &mut Client,
+    notifications_config: &NotificationsConfig,
 ) -> anyhow::Result<()> {
     info!("Storing uptime...");
 
-    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
-    let snapshot: ValidatorsPerformanceSnapshot = serde_yaml::from_reader(snapshot_file)?;
     let mut validators_with_extended_status: HashSet<String> = HashSet::new();
     let snapshot_epoch: Decimal = snapshot.epoch.into();
     let snapshot_created_at = snapshot.created_at.parse::<DateTime<Utc>>().unwrap();
@@ -42,9 +109,52 @@ pub async fn store_uptime(
         .unwrap();
     let status_max_delay_to_extend = Duration::minutes(5);
     let mut records_extensions: HashMap<i64, DateTime<Utc>> = Default::default();
+    let mut previous_statuses: HashMap<String, String> = Default::default();
+    let mut collection_gaps: Vec<(String, Decimal, DateTime<Utc>)> = Default::default();
+    let mut events: Vec<ValidatorEvent> = Default::default();
 
     info!("Loaded the snapshot");
 
+    let mut previous_samples: HashMap<String, PreviousSample> = Default::default();
+    for row in psql_client
+        .query(
+            "
+        SELECT DISTINCT ON (vote_account)
+            vote_account,
+            slot,
+            credits
+        FROM uptime_samples
+        WHERE epoch = $1
+        ORDER BY vote_account, created_at DESC
+    ",
+            &[&snapshot_epoch],
+        )
+        .await?
+    {
+        previous_samples.insert(
+            row.get("vote_account"),
+            PreviousSample {
+                slot: row.get::<_, Decimal>("slot").try_into()?,
+                credits: row.get::<_, Decimal>("credits").try_into()?,
+            },
+        );
+    }
+
+    let statuses: HashMap<&String, &'static str> = snapshot
+        .validators
+        .iter()
+        .map(|(vote_account, validator_snapshot)| {
+            (
+                vote_account,
+                status_from_sample(
+                    snapshot,
+                    validator_snapshot,
+                    previous_samples.get(vote_account),
+                ),
+            )
+        })
+        .collect();
+
     for row in psql_client
         .query(
             "
@@ -63,7 +173,7 @@ pub async fn store_uptime(
         .await?
     {
         let id: i64 = row.get("id");
-        let vote_account: &str = row.get("vote_account");
+        let vote_account: String = row.get("vote_account");
         let status: &str = row.get("status");
         let epoch: Decimal = row.get("epoch");
         let start_at: DateTime<Utc> = row.get("start_at");
@@ -71,16 +181,19 @@ pub async fn store_uptime(
         let latest_end_extension_at = end_at
             .checked_add_signed(status_max_delay_to_extend.clone())
             .unwrap();
+        previous_statuses.insert(vote_account.clone(), status.to_string());
 
-        if let Some(validator_snapshot) = snapshot.validators.get(vote_account) {
-            let status_from_snapshot = status_from_delinquency(validator_snapshot.delinquent);
+        if let Some(status_from_snapshot) = statuses.get(&vote_account) {
             if latest_end_extension_at > snapshot_created_at {
-                if status == status_from_snapshot && epoch == snapshot_epoch {
-                    validators_with_extended_status.insert(vote_account.to_string());
+                if status == *status_from_snapshot && epoch == snapshot_epoch {
+                    validators_with_extended_status.insert(vote_account.clone());
                     records_extensions.insert(id, default_status_end_at.clone());
                 } else {
                     records_extensions.insert(id, snapshot_created_at.clone());
                 }
+            } else {
+                // Nothing was collected in between, the status is not known instead of being extended
+                collection_gaps.push((vote_account.clone(), epoch, end_at));
             }
         }
 
@@ -112,27 +225,47 @@ pub async fn store_uptime(
         "vote_account, status, epoch, start_at, end_at".to_string(),
     );
 
-    for (vote_account, snapshot) in snapshot.validators.iter() {
-        if !validators_with_extended_status.contains(vote_account) {
-            if snapshot.delinquent {
-                let mut params: Vec<&(dyn ToSql + Sync)> = vec![
-                    vote_account,
-                    &DOWN,
-                    &snapshot_epoch,
-                    &snapshot_created_at,
-                    &default_status_end_at,
-                ];
-                query.add(&mut params);
+    for (vote_account, epoch, gap_start_at) in collection_gaps.iter() {
+        let mut params: Vec<&(dyn ToSql + Sync)> = vec![
+            vote_account,
+            &UNKNOWN,
+            epoch,
+            gap_start_at,
+            &snapshot_created_at,
+        ];
+        query.add(&mut params);
+    }
+    info!("Found {} collection gaps", collection_gaps.len());
+
+    for (vote_account, status) in statuses.iter() {
+        if !validators_with_extended_status.contains(*vote_account) {
+            if let Some(previous_status) = previous_statuses.get(*vote_account) {
+                if previous_status != status {
+                    events.push(ValidatorEvent {
+                        vote_account: vote_account.to_string(),
+                        event_type: if *status == DOWN {
+                            ValidatorEventType::DowntimeStart
+                        } else {
+                            ValidatorEventType::DowntimeEnd
+                        },
+                        epoch: snapshot.epoch,
+                        value_before: Some(previous_status.clone()),
+                        value_after: Some(status.to_string()),
+                        created_at: snapshot_created_at,
+                    });
+                }
+            }
+            let mut params: Vec<&(dyn ToSql + Sync)> = vec![
+                *vote_account,
+                status,
+                &snapshot_epoch,
+                &snapshot_created_at,
+                &default_status_end_at,
+            ];
+            query.add(&mut params);
+            if *status == DOWN {
                 warn!("Validator {} is now DOWN", vote_account);
             } else {
-                let mut params: Vec<&(dyn ToSql + Sync)> = vec![
-                    vote_account,
-                    &UP,
-                    &snapshot_epoch,
-                    &snapshot_created_at,
-                    &default_status_end_at,
-                ];
-                query.add(&mut params);
                 info!("Validator {} is now UP", vote_account);
             }
         }
@@ -140,5 +273,68 @@ pub async fn store_uptime(
     let insertions = query.execute(&mut psql_client).await?;
     info!("Stored {} changed uptimes", insertions.unwrap_or(0));
 
+    if snapshot.slot > 0 {
+        store_uptime_samples(snapshot, &statuses, psql_client).await?;
+    }
+
+    notify(psql_client, notifications_config, events).await?;
+
+    Ok(())
+}
+
+async fn store_uptime_samples(
+    snapshot: &ValidatorsPerformanceSnapshot,
+    statuses: &HashMap<&String, &'static str>,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    let snapshot_epoch: Decimal = snapshot.epoch.into();
+    let snapshot_created_at = snapshot.created_at.parse::<DateTime<Utc>>()?;
+    let samples: Vec<_> = snapshot
+        .validators
+        .iter()
+        .map(|(vote_account, validator_snapshot)| UptimeSample {
+            vote_account: vote_account.clone(),
+            status: statuses.get(vote_account).cloned().unwrap_or(UP),
+            slot: snapshot.slot.into(),
+            last_vote: validator_snapshot.last_vote.into(),
+            root_slot: validator_snapshot.root_slot.into(),
+            vote_lag: snapshot
+                .slot
+                .saturating_sub(validator_snapshot.last_vote)
+                .into(),
+            root_lag: snapshot
+                .slot
+                .saturating_sub(validator_snapshot.root_slot)
+                .into(),
+            credits: validator_snapshot.credits.into(),
+        })
+        .collect();
+
+    let mut insertions = 0;
+    for chunk in samples.chunks(DEFAULT_CHUNK_SIZE) {
+        let mut query = InsertQueryCombiner::new(
+            "uptime_samples".to_string(),
+            "vote_account, epoch, status, slot, last_vote, root_slot, vote_lag, root_lag, credits, created_at"
+                .to_string(),
+        );
+        for sample in chunk {
+            let mut params: Vec<&(dyn ToSql + Sync)> = vec![
+                &sample.vote_account,
+                &snapshot_epoch,
+                &sample.status,
+                &sample.slot,
+                &sample.last_vote,
+                &sample.root_slot,
+                &sample.vote_lag,
+                &sample.root_lag,
+                &sample.credits,
+                &snapshot_created_at,
+            ];
+            query.add(&mut params);
+        }
+        insertions += query.execute(psql_client).await?.unwrap_or(0);
+    }
+    info!("Stored {} uptime samples", insertions);
+
     Ok(())
 }
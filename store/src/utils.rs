@@ -1,6 +1,9 @@
 use crate::dto::{
-    BlockProductionStats, ClusterStats, CommissionRecord, DCConcentrationStats, ScoringRunRecord,
-    UptimeRecord, ValidatorAggregatedFlat, ValidatorEpochStats, ValidatorRecord,
+    BlockProductionStats, ClusterStats, CommissionRecord, ConcentrationChange,
+    DCConcentrationStats, DecentralizationBreakdown, DecentralizationChange,
+    DecentralizationImpact, DecentralizationIndices, DecentralizationIndicesChange,
+    DecentralizationStats, EpochClusterStats, MevRecord, ScoringConfig, ScoringInputs,
+    ScoringRunRecord, UptimeRecord, ValidatorAggregatedFlat, ValidatorEpochStats, ValidatorRecord,
     ValidatorScoreRecord, ValidatorScoringCsvRow, ValidatorWarning, ValidatorsAggregated,
     VersionRecord,
 };
@@ -129,8 +132,17 @@ struct InflationApyCalculator {
     total_weighted_credits: u128,
 }
 impl InflationApyCalculator {
+    fn epochs_per_year(&self) -> f64 {
+        365.25 * 24f64 * 3600f64 / self.duration as f64
+    }
+
+    fn apy_from_apr(&self, apr: f64) -> f64 {
+        let epochs_per_year = self.epochs_per_year();
+        (1.0 + apr / epochs_per_year).powf(epochs_per_year - 1.0) - 1.0
+    }
+
     fn estimate_yields(&self, credits: u64, stake: u64, commission: u8) -> (f64, f64) {
-        let epochs_per_year = 365.25 * 24f64 * 3600f64 / self.duration as f64;
+        let epochs_per_year = self.epochs_per_year();
         let rewards_share = credits as f64 * stake as f64 / self.total_weighted_credits as f64;
         let inflation_change_per_epoch = (1.0 - self.inflation_taper).powf(1.0 / epochs_per_year);
         let generated_rewards =
@@ -139,9 +151,32 @@ impl InflationApyCalculator {
                 / (1.0 - inflation_change_per_epoch);
         let staker_rewards = generated_rewards * (1.0 - commission as f64 / 100.0);
         let apr = staker_rewards / stake as f64;
-        let apy = (1.0 + apr / epochs_per_year).powf(epochs_per_year - 1.0) - 1.0;
 
-        (apr, apy)
+        (apr, self.apy_from_apr(apr))
+    }
+
+    // MEV tips are not subject to the inflation taper, the stakers' share of the epoch's tips is simply annualized
+    fn estimate_mev_apr(&self, stake: u64, mev_rewards: u64, mev_commission_bps: u16) -> f64 {
+        if stake == 0 {
+            return 0.0;
+        }
+        let staker_mev_rewards = mev_rewards as f64 * (1.0 - mev_commission_bps as f64 / 10000.0);
+
+        staker_mev_rewards * self.epochs_per_year() / stake as f64
+    }
+
+    fn estimate_yields_with_mev(
+        &self,
+        credits: u64,
+        stake: u64,
+        commission: u8,
+        mev_rewards: u64,
+        mev_commission_bps: u16,
+    ) -> (f64, f64) {
+        let (inflation_apr, _) = self.estimate_yields(credits, stake, commission);
+        let apr = inflation_apr + self.estimate_mev_apr(stake, mev_rewards, mev_commission_bps);
+
+        (apr, self.apy_from_apr(apr))
     }
 }
 async fn get_apy_calculators(
@@ -285,6 +320,64 @@ pub async fn load_commissions(
     Ok(records)
 }
 
+pub async fn load_mev(
+    psql_client: &Client,
+    epochs: u64,
+) -> anyhow::Result<HashMap<String, Vec<MevRecord>>> {
+    let rows = psql_client
+        .query(
+            "
+            WITH cluster AS (SELECT MAX(epoch) as last_epoch FROM cluster_info)
+            SELECT
+                vote_account,
+                mev_commission,
+                total_epoch_rewards,
+                claimed_epoch_rewards,
+                total_epoch_claimants,
+                epoch_active_claimants,
+                epoch,
+                epoch_slot,
+                created_at
+            FROM mev, cluster
+            WHERE epoch > cluster.last_epoch - $1::NUMERIC
+            ORDER BY epoch DESC",
+            &[&Decimal::from(epochs)],
+        )
+        .await?;
+
+    let mut records: HashMap<_, Vec<_>> = Default::default();
+    for row in rows {
+        let vote_account: String = row.get("vote_account");
+        let mev = records
+            .entry(vote_account.clone())
+            .or_insert(Default::default());
+        mev.push(MevRecord {
+            epoch: row.get::<_, Decimal>("epoch").try_into()?,
+            epoch_slot: row.get::<_, Decimal>("epoch_slot").try_into()?,
+            mev_commission: row.get::<_, i32>("mev_commission").try_into()?,
+            total_epoch_rewards: row
+                .get::<_, Option<Decimal>>("total_epoch_rewards")
+                .map(|n| n.try_into())
+                .transpose()?,
+            claimed_epoch_rewards: row
+                .get::<_, Option<Decimal>>("claimed_epoch_rewards")
+                .map(|n| n.try_into())
+                .transpose()?,
+            total_epoch_claimants: row
+                .get::<_, Option<i32>>("total_epoch_claimants")
+                .map(|n| n.try_into())
+                .transpose()?,
+            epoch_active_claimants: row
+                .get::<_, Option<i32>>("epoch_active_claimants")
+                .map(|n| n.try_into())
+                .transpose()?,
+            created_at: row.get("created_at"),
+        })
+    }
+
+    Ok(records)
+}
+
 pub async fn update_with_warnings(
     validators: &mut HashMap<String, ValidatorRecord>,
 ) -> anyhow::Result<()> {
@@ -332,6 +425,13 @@ pub fn update_validators_with_avgs(validators: &mut HashMap<String, ValidatorRec
                 .flat_map(|epoch| epoch.apy)
                 .collect(),
         );
+        record.avg_apy_with_mev = average(
+            &record
+                .epoch_stats
+                .iter()
+                .flat_map(|epoch| epoch.apy_with_mev)
+                .collect(),
+        );
         record.avg_uptime_pct = average(
             &record
                 .epoch_stats
@@ -394,10 +494,11 @@ pub async fn load_validators(
         _ => return Ok(Default::default()),
     };
     let apy_calculators = get_apy_calculators(psql_client).await?;
-    let concentrations = load_dc_concentration_stats(psql_client, 1)
-        .await?
-        .first()
-        .cloned();
+    let concentrations = Some(
+        compute_cluster_stats(psql_client, last_epoch)
+            .await?
+            .dc_concentration,
+    );
 
     log::info!("Querying validators...");
     let rows = psql_client
@@ -405,9 +506,10 @@ pub async fn load_validators(
             "
             WITH
                 validators_aggregated AS (SELECT vote_account, MIN(epoch) first_epoch FROM validators GROUP BY vote_account),
-                cluster AS (SELECT MAX(epoch) as last_epoch FROM cluster_info)
+                cluster AS (SELECT MAX(epoch) as last_epoch FROM cluster_info),
+                mev_last AS (SELECT DISTINCT ON (vote_account, epoch) vote_account, epoch, mev_commission, total_epoch_rewards FROM mev ORDER BY vote_account, epoch, created_at DESC)
             SELECT
-                validators.identity, validators.vote_account, epoch,
+                validators.identity, validators.vote_account, validators.epoch,
 
                 info_name,
                 info_url,
@@ -441,13 +543,18 @@ pub async fn load_validators(
                 uptime_pct,
                 uptime,
                 downtime,
+                vote_latency,
+                vote_credits_ratio,
+                mev_last.mev_commission,
+                mev_last.total_epoch_rewards AS mev_rewards,
 
                 validators_aggregated.first_epoch AS first_epoch
             FROM validators
                 LEFT JOIN cluster ON 1 = 1
                 LEFT JOIN validators_aggregated ON validators_aggregated.vote_account = validators.vote_account
-            WHERE epoch > cluster.last_epoch - $1::NUMERIC
-            ORDER BY epoch DESC",
+                LEFT JOIN mev_last ON mev_last.vote_account = validators.vote_account AND mev_last.epoch = validators.epoch
+            WHERE validators.epoch > cluster.last_epoch - $1::NUMERIC
+            ORDER BY validators.epoch DESC",
             &[&Decimal::from(epochs)],
         )
         .await?;
@@ -459,17 +566,32 @@ pub async fn load_validators(
             let vote_account: String = row.get("vote_account");
             let epoch: u64 = row.get::<_, Decimal>("epoch").try_into().unwrap();
             let first_epoch: u64 = row.get::<_, Decimal>("first_epoch").try_into().unwrap();
-            let (apr, apy) = if let Some(c) = apy_calculators.get(&epoch) {
-                let (apr, apy) = c.estimate_yields(
-                    row.get::<_, Decimal>("credits").try_into().unwrap(),
-                    row.get::<_, Decimal>("activated_stake").try_into().unwrap(),
-                    row.get::<_, Option<i32>>("commission_effective")
+            let mev_commission: Option<u16> = row
+                .get::<_, Option<i32>>("mev_commission")
+                .map(|n| n.try_into().unwrap());
+            let mev_rewards: Option<u64> = row
+                .get::<_, Option<Decimal>>("mev_rewards")
+                .map(|n| n.try_into().unwrap());
+            let (apr, apy, apr_with_mev, apy_with_mev) = match apy_calculators.get(&epoch) {
+                Some(c) => {
+                    let credits = row.get::<_, Decimal>("credits").try_into().unwrap();
+                    let activated_stake =
+                        row.get::<_, Decimal>("activated_stake").try_into().unwrap();
+                    let commission = row
+                        .get::<_, Option<i32>>("commission_effective")
                         .map(|n| n.try_into().unwrap())
-                        .unwrap_or(100),
-                );
-                (Some(apr), Some(apy))
-            } else {
-                (None, None)
+                        .unwrap_or(100);
+                    let (apr, apy) = c.estimate_yields(credits, activated_stake, commission);
+                    let (apr_with_mev, apy_with_mev) = c.estimate_yields_with_mev(
+                        credits,
+                        activated_stake,
+                        commission,
+                        mev_rewards.unwrap_or(0),
+                        mev_commission.unwrap_or(10000),
+                    );
+                    (Some(apr), Some(apy), Some(apr_with_mev), Some(apy_with_mev))
+                }
+                None => (None, None, None, None),
             };
 
             let dc_full_city = row
@@ -549,6 +671,7 @@ pub async fn load_validators(
 
                     avg_uptime_pct: None,
                     avg_apy: None,
+                    avg_apy_with_mev: None,
                     has_last_epoch_stats: false,
                 });
             if last_epoch == epoch {
@@ -594,8 +717,14 @@ pub async fn load_validators(
                 downtime: row
                     .get::<_, Option<Decimal>>("downtime")
                     .map(|n| n.try_into().unwrap()),
+                vote_latency: row.get("vote_latency"),
+                vote_credits_ratio: row.get("vote_credits_ratio"),
+                mev_commission,
+                mev_rewards,
                 apr,
                 apy,
+                apr_with_mev,
+                apy_with_mev,
                 score: None,
                 rank_apy: None,
                 rank_score: None,
@@ -706,6 +835,12 @@ pub async fn load_scores_in_epochs(
     Ok(result)
 }
 
+pub fn parse_scoring_config(config: Option<String>) -> anyhow::Result<Option<ScoringConfig>> {
+    Ok(config
+        .map(|config| serde_yaml::from_str(&config))
+        .transpose()?)
+}
+
 pub async fn load_last_scoring_run(
     psql_client: &Client,
 ) -> anyhow::Result<Option<ScoringRunRecord>> {
@@ -719,7 +854,8 @@ pub async fn load_last_scoring_run(
                 epoch,
                 components,
                 component_weights,
-                ui_id
+                ui_id,
+                config
             FROM scoring_runs
             WHERE scoring_run_id IN (SELECT MAX(scoring_run_id) FROM scoring_runs)",
             &[],
@@ -741,6 +877,7 @@ pub async fn load_last_scoring_run(
         components: scoring_run.get("components"),
         component_weights: scoring_run.get("component_weights"),
         ui_id: scoring_run.get("ui_id"),
+        config: parse_scoring_config(scoring_run.get("config"))?,
     }))
 }
 
@@ -823,131 +960,623 @@ pub async fn get_last_epoch(psql_client: &Client) -> anyhow::Result<Option<u64>>
     Ok(row.map(|row| row.get::<_, Decimal>("last_epoch").try_into().unwrap()))
 }
 
-pub async fn load_dc_concentration_stats(
-    psql_client: &Client,
-    epochs: u64,
-) -> anyhow::Result<Vec<DCConcentrationStats>> {
-    let last_epoch = match get_last_epoch(psql_client).await? {
-        Some(last_epoch) => last_epoch,
-        _ => return Ok(Default::default()),
-    };
-    let first_epoch = last_epoch - epochs.min(last_epoch) + 1;
+const DC_DIMENSION_ASO: &str = "ASO";
+const DC_DIMENSION_ASN: &str = "ASN";
+const DC_DIMENSION_CITY: &str = "CITY";
+const DECENTRALIZATION_DIMENSION_VALIDATOR: &str = "VALIDATOR";
+const DECENTRALIZATION_DIMENSION_ASO: &str = "ASO";
+const DECENTRALIZATION_DIMENSION_COUNTRY: &str = "COUNTRY";
+const DECENTRALIZATION_DIMENSION_CITY: &str = "CITY";
+
+fn map_stake_to_concentration(
+    stake: &HashMap<String, u64>,
+    total_stake: u64,
+) -> HashMap<String, f64> {
+    stake
+        .iter()
+        .map(|(key, stake)| (key.clone(), *stake as f64 / total_stake as f64))
+        .collect()
+}
 
-    let mut stats: Vec<_> = Default::default();
+/// Number of the biggest validators holding together more than 1/3 of the stake (size of the superminority)
+pub fn nakamoto_coefficient(stakes: &[u64]) -> u64 {
+    let total_stake: u64 = stakes.iter().sum();
+    let mut stakes = stakes.to_vec();
+    stakes.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut cumulative_stake = 0;
+    let mut coefficient = 0;
+    for stake in stakes {
+        if cumulative_stake > total_stake / 3 {
+            break;
+        }
+        cumulative_stake += stake;
+        coefficient += 1;
+    }
 
-    let map_stake_to_concentration =
-        |stake: &HashMap<String, u64>, total_stake: u64| -> HashMap<_, _> {
-            stake
-                .iter()
-                .map(|(key, stake)| (key.clone(), *stake as f64 / total_stake as f64))
-                .collect()
-        };
+    coefficient
+}
 
-    for epoch in (first_epoch..=last_epoch).rev() {
-        let mut dc_stake_by_aso: HashMap<_, _> = Default::default();
-        let mut dc_stake_by_asn: HashMap<_, _> = Default::default();
-        let mut dc_stake_by_city: HashMap<_, _> = Default::default();
-        let mut total_active_stake = 0;
+pub fn herfindahl_hirschman_index(stakes: &[u64]) -> f64 {
+    let total_stake: u64 = stakes.iter().sum();
+    if total_stake == 0 {
+        return 0.0;
+    }
 
-        let rows = psql_client
-            .query(
-                "SELECT
-                    activated_stake,
-                    dc_aso,
-                    dc_asn,
-                    CONCAT(dc_continent, '/', dc_country, '/', dc_city) dc_full_city
-                FROM validators WHERE epoch = $1",
-                &[&Decimal::from(epoch)],
-            )
-            .await?;
+    stakes
+        .iter()
+        .map(|stake| (*stake as f64 / total_stake as f64).powi(2))
+        .sum()
+}
 
-        for row in rows.iter() {
-            let activated_stake: u64 = row.get::<_, Decimal>("activated_stake").try_into()?;
-            let dc_aso = row
-                .get::<_, Option<String>>("dc_aso")
-                .unwrap_or("Unknown".to_string());
-            let dc_asn: String = row
-                .get::<_, Option<i32>>("dc_asn")
-                .map_or("Unknown".to_string(), |dc_asn| dc_asn.to_string());
-            let dc_city: String = row
-                .get::<_, Option<String>>("dc_full_city")
-                .unwrap_or("Unknown".to_string());
-
-            total_active_stake += activated_stake;
-            *(dc_stake_by_aso.entry(dc_aso).or_insert(Default::default())) += activated_stake;
-            *(dc_stake_by_asn.entry(dc_asn).or_insert(Default::default())) += activated_stake;
-            *(dc_stake_by_city
-                .entry(dc_city)
-                .or_insert(Default::default())) += activated_stake;
+/// Computed from the stakes sorted in ascending order as `2 * sum(i * stake_i) / (n * total_stake) - (n + 1) / n`
+pub fn gini_coefficient(stakes: &[u64]) -> f64 {
+    let total_stake: u64 = stakes.iter().sum();
+    if total_stake == 0 {
+        return 0.0;
+    }
+    let mut stakes = stakes.to_vec();
+    stakes.sort_unstable();
+
+    let n = stakes.len() as f64;
+    let weighted_stake: f64 = stakes
+        .iter()
+        .enumerate()
+        .map(|(i, stake)| (i + 1) as f64 * *stake as f64)
+        .sum();
+
+    2.0 * weighted_stake / (n * total_stake as f64) - (n + 1.0) / n
+}
+
+pub fn decentralization_indices(stakes: &[u64]) -> DecentralizationIndices {
+    DecentralizationIndices {
+        nakamoto_coefficient: nakamoto_coefficient(stakes),
+        herfindahl_hirschman_index: herfindahl_hirschman_index(stakes),
+        gini_coefficient: gini_coefficient(stakes),
+    }
+}
+
+pub fn decentralization_indices_change(
+    before: &DecentralizationIndices,
+    after: &DecentralizationIndices,
+) -> DecentralizationIndicesChange {
+    DecentralizationIndicesChange {
+        nakamoto_coefficient: after.nakamoto_coefficient as i64
+            - before.nakamoto_coefficient as i64,
+        herfindahl_hirschman_index: after.herfindahl_hirschman_index
+            - before.herfindahl_hirschman_index,
+        gini_coefficient: after.gini_coefficient - before.gini_coefficient,
+    }
+}
+
+fn decentralization_breakdown(
+    indices: DecentralizationIndices,
+    indices_without_marinade: DecentralizationIndices,
+) -> DecentralizationBreakdown {
+    DecentralizationBreakdown {
+        marinade_impact: decentralization_indices_change(&indices_without_marinade, &indices),
+        indices,
+        indices_without_marinade,
+    }
+}
+
+/// Stake of a staked validator with the data center it is grouped by
+#[derive(Debug, Clone)]
+pub struct ValidatorStake {
+    pub vote_account: String,
+    pub dc_aso: String,
+    pub dc_country: String,
+    pub dc_city: String,
+    pub activated_stake: u64,
+    pub marinade_stake: u64,
+}
+
+fn sum_stakes_by<'a>(
+    validators: &'a [ValidatorStake],
+    key: impl Fn(&'a ValidatorStake) -> &'a String,
+    stake: impl Fn(&ValidatorStake) -> u64,
+) -> HashMap<&'a String, u64> {
+    let mut stakes: HashMap<&String, u64> = Default::default();
+    for validator in validators {
+        *stakes.entry(key(validator)).or_default() += stake(validator);
+    }
+
+    stakes
+}
+
+/// Sums the stakes (with and without the Marinade stake) of the validators by the key
+fn decentralization_breakdown_by<'a>(
+    validators: &'a [ValidatorStake],
+    key: impl Fn(&'a ValidatorStake) -> &'a String + Copy,
+) -> DecentralizationBreakdown {
+    let stakes: Vec<_> = sum_stakes_by(validators, key, |v| v.activated_stake)
+        .into_values()
+        .collect();
+    let stakes_without_marinade: Vec<_> = sum_stakes_by(validators, key, |v| {
+        v.activated_stake.saturating_sub(v.marinade_stake)
+    })
+    .into_values()
+    .collect();
+
+    decentralization_breakdown(
+        decentralization_indices(&stakes),
+        decentralization_indices(&stakes_without_marinade),
+    )
+}
+
+/// Vote accounts of the biggest validators holding together more than 1/3 of the stake
+pub fn superminority(validators: &[ValidatorStake]) -> HashSet<&String> {
+    let total_stake: u64 = validators.iter().map(|v| v.activated_stake).sum();
+    let mut validators: Vec<_> = validators.iter().collect();
+    validators.sort_unstable_by_key(|v| std::cmp::Reverse(v.activated_stake));
+
+    let mut cumulative_stake = 0;
+    let mut superminority: HashSet<_> = Default::default();
+    for validator in validators {
+        if cumulative_stake > total_stake / 3 {
+            break;
+        }
+        cumulative_stake += validator.activated_stake;
+        superminority.insert(&validator.vote_account);
+    }
+
+    superminority
+}
+
+fn decentralization_change_by<'a>(
+    before: &'a [ValidatorStake],
+    after: &'a [ValidatorStake],
+    key: impl Fn(&'a ValidatorStake) -> &'a String + Copy,
+) -> DecentralizationChange {
+    let indices_before = decentralization_indices(
+        &sum_stakes_by(before, key, |v| v.activated_stake)
+            .into_values()
+            .collect::<Vec<_>>(),
+    );
+    let indices_after = decentralization_indices(
+        &sum_stakes_by(after, key, |v| v.activated_stake)
+            .into_values()
+            .collect::<Vec<_>>(),
+    );
+
+    DecentralizationChange {
+        change: decentralization_indices_change(&indices_before, &indices_after),
+        indices_before,
+        indices_after,
+    }
+}
+
+fn concentration_changes_by<'a>(
+    before: &'a [ValidatorStake],
+    after: &'a [ValidatorStake],
+    key: impl Fn(&'a ValidatorStake) -> &'a String + Copy,
+) -> Vec<ConcentrationChange> {
+    let total_stake_before: u64 = before.iter().map(|v| v.activated_stake).sum();
+    let total_stake_after: u64 = after.iter().map(|v| v.activated_stake).sum();
+    let concentrations_before: HashMap<_, _> = sum_stakes_by(before, key, |v| v.activated_stake)
+        .into_iter()
+        .map(|(key, stake)| (key, stake as f64 / total_stake_before.max(1) as f64))
+        .collect();
+    let concentrations_after: HashMap<_, _> = sum_stakes_by(after, key, |v| v.activated_stake)
+        .into_iter()
+        .map(|(key, stake)| (key, stake as f64 / total_stake_after.max(1) as f64))
+        .collect();
+
+    let keys: HashSet<_> = concentrations_before
+        .keys()
+        .chain(concentrations_after.keys())
+        .collect();
+    let mut changes: Vec<_> = keys
+        .into_iter()
+        .map(|key| ConcentrationChange {
+            key: key.to_string(),
+            concentration_before: concentrations_before.get(key).cloned().unwrap_or(0.0),
+            concentration_after: concentrations_after.get(key).cloned().unwrap_or(0.0),
+        })
+        .filter(|change| change.concentration_before != change.concentration_after)
+        .collect();
+    changes.sort_by(|a, b| {
+        let change_a = (a.concentration_after - a.concentration_before).abs();
+        let change_b = (b.concentration_after - b.concentration_before).abs();
+        change_b
+            .total_cmp(&change_a)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+
+    changes
+}
+
+/// Compares the decentralization of the cluster with the validators staked as `before` and as `after`
+pub fn decentralization_impact(
+    before: &[ValidatorStake],
+    after: &[ValidatorStake],
+) -> DecentralizationImpact {
+    let superminority_before = superminority(before);
+    let superminority_after = superminority(after);
+
+    DecentralizationImpact {
+        by_validator: decentralization_change_by(before, after, |v| &v.vote_account),
+        by_aso: decentralization_change_by(before, after, |v| &v.dc_aso),
+        by_country: decentralization_change_by(before, after, |v| &v.dc_country),
+        by_city: decentralization_change_by(before, after, |v| &v.dc_city),
+        concentration_changes_by_aso: concentration_changes_by(before, after, |v| &v.dc_aso),
+        concentration_changes_by_country: concentration_changes_by(before, after, |v| {
+            &v.dc_country
+        }),
+        concentration_changes_by_city: concentration_changes_by(before, after, |v| &v.dc_city),
+        validators_entering_superminority: superminority_after
+            .difference(&superminority_before)
+            .count() as u64,
+        validators_leaving_superminority: superminority_before
+            .difference(&superminority_after)
+            .count() as u64,
+    }
+}
+
+pub fn decentralization_stats(epoch: u64, validators: &[ValidatorStake]) -> DecentralizationStats {
+    DecentralizationStats {
+        epoch,
+        by_validator: decentralization_breakdown_by(validators, |v| &v.vote_account),
+        by_aso: decentralization_breakdown_by(validators, |v| &v.dc_aso),
+        by_country: decentralization_breakdown_by(validators, |v| &v.dc_country),
+        by_city: decentralization_breakdown_by(validators, |v| &v.dc_city),
+    }
+}
+
+/// Aggregates the stored validators of the epoch, used for epochs without the stored cluster stats
+pub async fn compute_cluster_stats(
+    psql_client: &Client,
+    epoch: u64,
+) -> anyhow::Result<EpochClusterStats> {
+    let rows = psql_client
+        .query(
+            "SELECT
+                vote_account,
+                activated_stake,
+                marinade_stake,
+                blocks_produced,
+                leader_slots,
+                dc_aso,
+                dc_asn,
+                dc_country,
+                CONCAT(dc_continent, '/', dc_country, '/', dc_city) dc_full_city
+            FROM validators WHERE epoch = $1",
+            &[&Decimal::from(epoch)],
+        )
+        .await?;
+
+    let mut dc_stake_by_aso: HashMap<_, _> = Default::default();
+    let mut dc_stake_by_asn: HashMap<_, _> = Default::default();
+    let mut dc_stake_by_city: HashMap<_, _> = Default::default();
+    let mut validator_stakes: Vec<ValidatorStake> = Default::default();
+    let mut blocks_produced: u64 = 0;
+    let mut leader_slots: u64 = 0;
+
+    for row in rows.iter() {
+        let activated_stake: u64 = row.get::<_, Decimal>("activated_stake").try_into()?;
+        let dc_aso = row
+            .get::<_, Option<String>>("dc_aso")
+            .unwrap_or("Unknown".to_string());
+        let dc_asn: String = row
+            .get::<_, Option<i32>>("dc_asn")
+            .map_or("Unknown".to_string(), |dc_asn| dc_asn.to_string());
+        let dc_city: String = row
+            .get::<_, Option<String>>("dc_full_city")
+            .unwrap_or("Unknown".to_string());
+
+        if activated_stake > 0 {
+            validator_stakes.push(ValidatorStake {
+                vote_account: row.get("vote_account"),
+                dc_aso: dc_aso.clone(),
+                dc_country: row
+                    .get::<_, Option<String>>("dc_country")
+                    .unwrap_or("Unknown".to_string()),
+                dc_city: dc_city.clone(),
+                activated_stake,
+                marinade_stake: row.get::<_, Decimal>("marinade_stake").try_into()?,
+            });
         }
+        blocks_produced += u64::try_from(row.get::<_, Decimal>("blocks_produced"))?;
+        leader_slots += u64::try_from(row.get::<_, Decimal>("leader_slots"))?;
+        *(dc_stake_by_aso.entry(dc_aso).or_insert(Default::default())) += activated_stake;
+        *(dc_stake_by_asn.entry(dc_asn).or_insert(Default::default())) += activated_stake;
+        *(dc_stake_by_city
+            .entry(dc_city)
+            .or_insert(Default::default())) += activated_stake;
+    }
+    let total_activated_stake = validator_stakes.iter().map(|v| v.activated_stake).sum();
 
-        stats.push(DCConcentrationStats {
+    Ok(EpochClusterStats {
+        block_production: BlockProductionStats {
+            epoch,
+            blocks_produced,
+            leader_slots,
+            avg_skip_rate: if leader_slots > 0 {
+                1.0 - blocks_produced as f64 / leader_slots as f64
+            } else {
+                0.0
+            },
+        },
+        dc_concentration: DCConcentrationStats {
             epoch,
-            total_activated_stake: Default::default(),
+            total_activated_stake,
             dc_concentration_by_aso: map_stake_to_concentration(
                 &dc_stake_by_aso,
-                total_active_stake,
+                total_activated_stake,
             ),
+            dc_stake_by_aso,
             dc_concentration_by_asn: map_stake_to_concentration(
                 &dc_stake_by_asn,
-                total_active_stake,
+                total_activated_stake,
             ),
             dc_stake_by_asn,
-            dc_stake_by_aso,
             dc_concentration_by_city: map_stake_to_concentration(
                 &dc_stake_by_city,
-                total_active_stake,
+                total_activated_stake,
             ),
             dc_stake_by_city,
-        })
+        },
+        decentralization: decentralization_stats(epoch, &validator_stakes),
+    })
+}
+
+/// Replaces the stored cluster stats of the epoch
+pub async fn store_cluster_stats(
+    psql_client: &mut Client,
+    stats: &EpochClusterStats,
+) -> anyhow::Result<()> {
+    let epoch = Decimal::from(stats.block_production.epoch);
+    let mut dimensions: Vec<&str> = Default::default();
+    let mut dcs: Vec<&String> = Default::default();
+    let mut dc_stakes: Vec<Decimal> = Default::default();
+    for (dimension, dc_stake) in [
+        (DC_DIMENSION_ASO, &stats.dc_concentration.dc_stake_by_aso),
+        (DC_DIMENSION_ASN, &stats.dc_concentration.dc_stake_by_asn),
+        (DC_DIMENSION_CITY, &stats.dc_concentration.dc_stake_by_city),
+    ] {
+        for (dc, stake) in dc_stake {
+            dimensions.push(dimension);
+            dcs.push(dc);
+            dc_stakes.push(Decimal::from(*stake));
+        }
     }
 
-    Ok(stats)
+    let transaction = psql_client.transaction().await?;
+    transaction
+        .execute("DELETE FROM cluster_stats WHERE epoch = $1", &[&epoch])
+        .await?;
+    transaction
+        .execute("DELETE FROM cluster_dc_stakes WHERE epoch = $1", &[&epoch])
+        .await?;
+    transaction
+        .execute(
+            "DELETE FROM cluster_decentralization WHERE epoch = $1",
+            &[&epoch],
+        )
+        .await?;
+    transaction
+        .execute(
+            "INSERT INTO cluster_stats (epoch, total_activated_stake, blocks_produced, leader_slots, avg_skip_rate, created_at)
+            VALUES ($1, $2, $3, $4, $5, now())",
+            &[
+                &epoch,
+                &Decimal::from(stats.dc_concentration.total_activated_stake),
+                &Decimal::from(stats.block_production.blocks_produced),
+                &Decimal::from(stats.block_production.leader_slots),
+                &stats.block_production.avg_skip_rate,
+            ],
+        )
+        .await?;
+    transaction
+        .execute(
+            "INSERT INTO cluster_dc_stakes (epoch, dimension, dc, activated_stake)
+            SELECT $1, * FROM UNNEST($2::TEXT[], $3::TEXT[], $4::NUMERIC[])",
+            &[&epoch, &dimensions, &dcs, &dc_stakes],
+        )
+        .await?;
+    for (dimension, breakdown) in [
+        (
+            DECENTRALIZATION_DIMENSION_VALIDATOR,
+            &stats.decentralization.by_validator,
+        ),
+        (
+            DECENTRALIZATION_DIMENSION_ASO,
+            &stats.decentralization.by_aso,
+        ),
+        (
+            DECENTRALIZATION_DIMENSION_COUNTRY,
+            &stats.decentralization.by_country,
+        ),
+        (
+            DECENTRALIZATION_DIMENSION_CITY,
+            &stats.decentralization.by_city,
+        ),
+    ] {
+        transaction
+            .execute(
+                "INSERT INTO cluster_decentralization (epoch, dimension, nakamoto_coefficient, herfindahl_hirschman_index, gini_coefficient, nakamoto_coefficient_without_marinade, herfindahl_hirschman_index_without_marinade, gini_coefficient_without_marinade)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+                &[
+                    &epoch,
+                    &dimension,
+                    &i32::try_from(breakdown.indices.nakamoto_coefficient)?,
+                    &breakdown.indices.herfindahl_hirschman_index,
+                    &breakdown.indices.gini_coefficient,
+                    &i32::try_from(breakdown.indices_without_marinade.nakamoto_coefficient)?,
+                    &breakdown.indices_without_marinade.herfindahl_hirschman_index,
+                    &breakdown.indices_without_marinade.gini_coefficient,
+                ],
+            )
+            .await?;
+    }
+    transaction.commit().await?;
+
+    log::info!(
+        "Stored cluster stats of epoch {} with {} data center stakes",
+        stats.block_production.epoch,
+        dcs.len()
+    );
+
+    Ok(())
 }
 
-pub async fn load_block_production_stats(
+async fn load_stored_cluster_stats(
     psql_client: &Client,
-    epochs: u64,
-) -> anyhow::Result<Vec<BlockProductionStats>> {
-    let last_epoch = match get_last_epoch(psql_client).await? {
-        Some(last_epoch) => last_epoch,
-        _ => return Ok(Default::default()),
-    };
-    let first_epoch = last_epoch - epochs.min(last_epoch) + 1;
+    first_epoch: u64,
+    last_epoch: u64,
+) -> anyhow::Result<HashMap<u64, EpochClusterStats>> {
+    let epochs_range: [&(dyn ToSql + Sync); 2] =
+        [&Decimal::from(first_epoch), &Decimal::from(last_epoch)];
+    let rows = psql_client
+        .query(
+            "SELECT epoch, total_activated_stake, blocks_produced, leader_slots, avg_skip_rate
+            FROM cluster_stats WHERE epoch BETWEEN $1 AND $2",
+            &epochs_range,
+        )
+        .await?;
 
-    let mut stats: Vec<_> = Default::default();
+    let mut stats: HashMap<u64, EpochClusterStats> = Default::default();
+    for row in rows {
+        let epoch: u64 = row.get::<_, Decimal>("epoch").try_into()?;
+        stats.insert(
+            epoch,
+            EpochClusterStats {
+                block_production: BlockProductionStats {
+                    epoch,
+                    blocks_produced: row.get::<_, Decimal>("blocks_produced").try_into()?,
+                    leader_slots: row.get::<_, Decimal>("leader_slots").try_into()?,
+                    avg_skip_rate: row.get("avg_skip_rate"),
+                },
+                dc_concentration: DCConcentrationStats {
+                    epoch,
+                    total_activated_stake: row
+                        .get::<_, Decimal>("total_activated_stake")
+                        .try_into()?,
+                    dc_concentration_by_aso: Default::default(),
+                    dc_stake_by_aso: Default::default(),
+                    dc_concentration_by_asn: Default::default(),
+                    dc_stake_by_asn: Default::default(),
+                    dc_concentration_by_city: Default::default(),
+                    dc_stake_by_city: Default::default(),
+                },
+                decentralization: DecentralizationStats {
+                    epoch,
+                    ..Default::default()
+                },
+            },
+        );
+    }
 
     let rows = psql_client
-            .query(
-                "SELECT
-	                epoch,
-                    COALESCE(SUM(blocks_produced), 0) blocks_produced,
-                    COALESCE(SUM(leader_slots), 0) leader_slots,
-                    (1 - COALESCE(SUM(blocks_produced), 0)  / coalesce(SUM(leader_slots), 1))::DOUBLE PRECISION avg_skip_rate
-                FROM validators
-                WHERE epoch > $1
-                GROUP BY epoch ORDER BY epoch DESC",
-                &[&Decimal::from(first_epoch)],
-            )
-            .await?;
+        .query(
+            "SELECT epoch, dimension, dc, activated_stake
+            FROM cluster_dc_stakes WHERE epoch BETWEEN $1 AND $2",
+            &epochs_range,
+        )
+        .await?;
+    for row in rows {
+        let epoch: u64 = row.get::<_, Decimal>("epoch").try_into()?;
+        let dc_concentration = match stats.get_mut(&epoch) {
+            Some(stats) => &mut stats.dc_concentration,
+            None => continue,
+        };
+        let dc_stake = match row.get::<_, &str>("dimension") {
+            DC_DIMENSION_ASO => &mut dc_concentration.dc_stake_by_aso,
+            DC_DIMENSION_ASN => &mut dc_concentration.dc_stake_by_asn,
+            DC_DIMENSION_CITY => &mut dc_concentration.dc_stake_by_city,
+            _ => continue,
+        };
+        dc_stake.insert(
+            row.get("dc"),
+            row.get::<_, Decimal>("activated_stake").try_into()?,
+        );
+    }
 
+    let rows = psql_client
+        .query(
+            "SELECT epoch, dimension, nakamoto_coefficient, herfindahl_hirschman_index, gini_coefficient, nakamoto_coefficient_without_marinade, herfindahl_hirschman_index_without_marinade, gini_coefficient_without_marinade
+            FROM cluster_decentralization WHERE epoch BETWEEN $1 AND $2",
+            &epochs_range,
+        )
+        .await?;
     for row in rows {
-        stats.push(BlockProductionStats {
-            epoch: row.get::<_, Decimal>("epoch").try_into()?,
-            blocks_produced: row.get::<_, Decimal>("blocks_produced").try_into()?,
-            leader_slots: row.get::<_, Decimal>("leader_slots").try_into()?,
-            avg_skip_rate: row.get("avg_skip_rate"),
-        })
+        let epoch: u64 = row.get::<_, Decimal>("epoch").try_into()?;
+        let decentralization = match stats.get_mut(&epoch) {
+            Some(stats) => &mut stats.decentralization,
+            None => continue,
+        };
+        let breakdown = match row.get::<_, &str>("dimension") {
+            DECENTRALIZATION_DIMENSION_VALIDATOR => &mut decentralization.by_validator,
+            DECENTRALIZATION_DIMENSION_ASO => &mut decentralization.by_aso,
+            DECENTRALIZATION_DIMENSION_COUNTRY => &mut decentralization.by_country,
+            DECENTRALIZATION_DIMENSION_CITY => &mut decentralization.by_city,
+            _ => continue,
+        };
+        *breakdown = decentralization_breakdown(
+            DecentralizationIndices {
+                nakamoto_coefficient: row.get::<_, i32>("nakamoto_coefficient").try_into()?,
+                herfindahl_hirschman_index: row.get("herfindahl_hirschman_index"),
+                gini_coefficient: row.get("gini_coefficient"),
+            },
+            DecentralizationIndices {
+                nakamoto_coefficient: row
+                    .get::<_, i32>("nakamoto_coefficient_without_marinade")
+                    .try_into()?,
+                herfindahl_hirschman_index: row.get("herfindahl_hirschman_index_without_marinade"),
+                gini_coefficient: row.get("gini_coefficient_without_marinade"),
+            },
+        );
+    }
+
+    for stats in stats.values_mut() {
+        let dc_concentration = &mut stats.dc_concentration;
+        let total_activated_stake = dc_concentration.total_activated_stake;
+        dc_concentration.dc_concentration_by_aso =
+            map_stake_to_concentration(&dc_concentration.dc_stake_by_aso, total_activated_stake);
+        dc_concentration.dc_concentration_by_asn =
+            map_stake_to_concentration(&dc_concentration.dc_stake_by_asn, total_activated_stake);
+        dc_concentration.dc_concentration_by_city =
+            map_stake_to_concentration(&dc_concentration.dc_stake_by_city, total_activated_stake);
     }
 
     Ok(stats)
 }
 
+/// Closed epochs are read from the stored cluster stats, the open epoch is computed from the validators
 pub async fn load_cluster_stats(psql_client: &Client, epochs: u64) -> anyhow::Result<ClusterStats> {
-    Ok(ClusterStats {
-        block_production_stats: load_block_production_stats(psql_client, epochs).await?,
-        dc_concentration_stats: load_dc_concentration_stats(psql_client, epochs).await?,
-    })
+    let mut cluster_stats = ClusterStats {
+        block_production_stats: Default::default(),
+        dc_concentration_stats: Default::default(),
+        decentralization_stats: Default::default(),
+    };
+    let last_epoch = match get_last_epoch(psql_client).await? {
+        Some(last_epoch) => last_epoch,
+        _ => return Ok(cluster_stats),
+    };
+    let first_epoch = last_epoch - epochs.min(last_epoch) + 1;
+
+    let mut stored_stats = load_stored_cluster_stats(psql_client, first_epoch, last_epoch).await?;
+    for epoch in (first_epoch..=last_epoch).rev() {
+        let stats = match stored_stats.remove(&epoch) {
+            Some(stats) => stats,
+            None => compute_cluster_stats(psql_client, epoch).await?,
+        };
+        // Nothing was collected in the epoch
+        if stats.dc_concentration.total_activated_stake == 0 {
+            continue;
+        }
+        cluster_stats
+            .block_production_stats
+            .push(stats.block_production);
+        cluster_stats
+            .dc_concentration_stats
+            .push(stats.dc_concentration);
+        cluster_stats
+            .decentralization_stats
+            .push(stats.decentralization);
+    }
+
+    Ok(cluster_stats)
 }
 
 pub fn aggregate_validators(
@@ -1009,6 +1638,7 @@ pub async fn load_validators_aggregated_flat(
                     coalesce(avg(case when leader_slots < 200 then least(skip_rate, cluster_skip_rate.stake_weighted_skip_rate) else skip_rate end), 1)::double precision as avg_grace_skip_rate,		
                     max(coalesce(commission_effective, commission_advertised, 100)) as max_commission,
                     (coalesce(avg(credits * greatest(0, 100 - coalesce(commission_effective, commission_advertised, 100))), 0) / 100)::double precision as avg_adjusted_credits,
+                    avg(vote_latency)::double precision as avg_vote_latency,
                     coalesce((array_agg(validators.dc_aso ORDER BY validators.epoch DESC))[1], 'Unknown') dc_aso,
                     coalesce((array_agg(mnde_votes ORDER BY validators.epoch DESC))[1], 0) as mnde_votes,
                     coalesce((array_agg((marinade_stake / 1e9)::double precision ORDER BY validators.epoch DESC))[1], 0) as marinade_stake,
@@ -1039,6 +1669,7 @@ pub async fn load_validators_aggregated_flat(
             avg_grace_skip_rate: row.get("avg_grace_skip_rate"),
             max_commission: row.get::<_, i32>("max_commission").try_into()?,
             avg_adjusted_credits: row.get("avg_adjusted_credits"),
+            avg_vote_latency: row.get("avg_vote_latency"),
             dc_aso: row.get("dc_aso"),
             mnde_votes: row.get::<_, Decimal>("mnde_votes").try_into()?,
             marinade_stake: row.get("marinade_stake"),
@@ -1059,24 +1690,91 @@ fn map_to_ordered_component_values(
             "COMMISSION_ADJUSTED_CREDITS" => Some(row.avg_adjusted_credits.to_string()),
             "GRACE_SKIP_RATE" => Some(row.avg_grace_skip_rate.to_string()),
             "DC_CONCENTRATION" => Some(row.avg_dc_concentration.to_string()),
+            "VOTE_LATENCY" => row.avg_vote_latency.map(|latency| latency.to_string()),
             _ => None,
         })
         .collect()
 }
 
+fn map_to_ordered_component_scores(
+    components: &Vec<&str>,
+    row: &ValidatorScoringCsvRow,
+) -> Vec<f64> {
+    components
+        .iter()
+        .map(|component| match *component {
+            "COMMISSION_ADJUSTED_CREDITS" => row.normalized_adjusted_credits,
+            "GRACE_SKIP_RATE" => row.normalized_grace_skip_rate,
+            "DC_CONCENTRATION" => row.normalized_dc_concentration,
+            "VOTE_LATENCY" => row.normalized_vote_latency,
+            _ => 0.0,
+        })
+        .collect()
+}
+
+fn map_to_ordered_component_ranks(
+    components: &Vec<&str>,
+    row: &ValidatorScoringCsvRow,
+) -> Vec<i32> {
+    components
+        .iter()
+        .map(|component| match *component {
+            "COMMISSION_ADJUSTED_CREDITS" => row.rank_adjusted_credits,
+            "GRACE_SKIP_RATE" => row.rank_grace_skip_rate,
+            "DC_CONCENTRATION" => row.rank_dc_concentration,
+            "VOTE_LATENCY" => row.rank_vote_latency,
+            _ => 0,
+        })
+        .collect()
+}
+
+pub fn score_record_from_csv_row(
+    components: &Vec<&str>,
+    row: &ValidatorScoringCsvRow,
+    scoring_run_id: i64,
+) -> anyhow::Result<ValidatorScoreRecord> {
+    Ok(ValidatorScoreRecord {
+        vote_account: row.vote_account.clone(),
+        score: row.score,
+        rank: row.rank,
+        mnde_votes: row.mnde_votes.try_into()?,
+        ui_hints: if row.ui_hints.is_empty() {
+            Default::default()
+        } else {
+            row.ui_hints.split(',').map(String::from).collect()
+        },
+        component_scores: map_to_ordered_component_scores(components, row),
+        component_ranks: map_to_ordered_component_ranks(components, row),
+        component_values: map_to_ordered_component_values(components, row),
+        eligible_stake_algo: row.eligible_stake_algo,
+        eligible_stake_mnde: row.eligible_stake_mnde,
+        eligible_stake_msol: row.eligible_stake_msol,
+        target_stake_algo: row.target_stake_algo.try_into()?,
+        target_stake_mnde: row.target_stake_mnde.try_into()?,
+        target_stake_msol: row.target_stake_msol.try_into()?,
+        scoring_run_id,
+    })
+}
+
+/// Config and inputs are stored with the run so that the run can be reproduced
+#[allow(clippy::too_many_arguments)]
 pub async fn store_scoring(
     mut psql_client: &mut Client,
     epoch: i32,
     ui_id: String,
     components: Vec<&str>,
     component_weights: Vec<f64>,
+    config: Option<&ScoringConfig>,
+    inputs: Option<&ScoringInputs>,
     scores: Vec<crate::dto::ValidatorScoringCsvRow>,
 ) -> anyhow::Result<()> {
+    let config = config.map(serde_yaml::to_string).transpose()?;
+    let inputs = inputs.map(serde_yaml::to_string).transpose()?;
     let scoring_run_result = psql_client
         .query_one(
-            "INSERT INTO scoring_runs (created_at, epoch, components, component_weights, ui_id)
-            VALUES (now(), $1, $2, $3, $4) RETURNING scoring_run_id;",
-            &[&epoch, &components, &component_weights, &ui_id],
+            "INSERT INTO scoring_runs (created_at, epoch, components, component_weights, ui_id, config, inputs)
+            VALUES (now(), $1, $2, $3, $4, $5, $6) RETURNING scoring_run_id;",
+            &[&epoch, &components, &component_weights, &ui_id, &config, &inputs],
         )
         .await?;
 
@@ -1089,11 +1787,7 @@ pub async fn store_scoring(
         .map(|row| {
             (
                 row.vote_account.clone(),
-                Vec::from([
-                    row.normalized_adjusted_credits,
-                    row.normalized_grace_skip_rate,
-                    row.normalized_dc_concentration,
-                ]),
+                map_to_ordered_component_scores(&components, row),
             )
         })
         .collect();
@@ -1103,11 +1797,7 @@ pub async fn store_scoring(
         .map(|row| {
             (
                 row.vote_account.clone(),
-                Vec::from([
-                    row.rank_adjusted_credits,
-                    row.rank_grace_skip_rate,
-                    row.rank_dc_concentration,
-                ]),
+                map_to_ordered_component_ranks(&components, row),
             )
         })
         .collect();
@@ -6,6 +6,8 @@ use log::info;
 use rust_decimal::prelude::*;
 use serde_yaml;
 use std::collections::{HashMap, HashSet};
+use store::dto::{NotificationsConfig, ValidatorEvent, ValidatorEventType};
+use store::notifications::{load_notifications_config, notify};
 use structopt::StructOpt;
 use tokio_postgres::types::ToSql;
 use tokio_postgres::Client;
@@ -14,18 +16,37 @@ use tokio_postgres::Client;
 pub struct StoreValidatorsOptions {
     #[structopt(long = "snapshot-file")]
     snapshot_path: String,
+
+    #[structopt(
+        long = "notifications-config",
+        help = "Validator events are sent to the sinks configured in this file"
+    )]
+    notifications_config_path: Option<String>,
 }
 
 const DEFAULT_CHUNK_SIZE: usize = 500;
 
 pub async fn store_validators(
     options: StoreValidatorsOptions,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    let notifications_config = match &options.notifications_config_path {
+        Some(path) => load_notifications_config(path)?,
+        None => Default::default(),
+    };
+    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
+    let snapshot: Snapshot = serde_yaml::from_reader(snapshot_file)?;
+
+    store_validators_snapshot(&snapshot, psql_client, &notifications_config).await
+}
+
+pub async fn store_validators_snapshot(
+    snapshot: &Snapshot,
     mut psql_client: &mut Client,
+    notifications_config: &NotificationsConfig,
 ) -> anyhow::Result<()> {
     info!("Storing validators snapshot...");
 
-    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
-    let snapshot: Snapshot = serde_yaml::from_reader(snapshot_file)?;
     let snapshot_created_at = snapshot.created_at.parse::<DateTime<Utc>>().unwrap();
 
     let validators: HashMap<_, _> = snapshot
@@ -43,6 +64,45 @@ pub async fn store_validators(
 
     info!("Loaded the snapshot");
 
+    let previous_superminority: HashMap<String, bool> = psql_client
+        .query(
+            "
+        SELECT DISTINCT ON (vote_account)
+            vote_account,
+            superminority
+        FROM validators
+        WHERE epoch <= $1
+        ORDER BY vote_account, epoch DESC
+    ",
+            &[&snapshot_epoch],
+        )
+        .await?
+        .iter()
+        .map(|row| (row.get("vote_account"), row.get("superminority")))
+        .collect();
+
+    let events: Vec<_> = validators
+        .iter()
+        .filter_map(|(vote_account, validator)| {
+            let was_superminority = *previous_superminority.get(vote_account)?;
+            if was_superminority == validator.superminority {
+                return None;
+            }
+            Some(ValidatorEvent {
+                vote_account: vote_account.clone(),
+                event_type: if validator.superminority {
+                    ValidatorEventType::SuperminorityEnter
+                } else {
+                    ValidatorEventType::SuperminorityLeave
+                },
+                epoch: snapshot.epoch,
+                value_before: Some(was_superminority.to_string()),
+                value_after: Some(validator.superminority.to_string()),
+                created_at: snapshot_created_at,
+            })
+        })
+        .collect();
+
     for chunk in psql_client
         .query(
             "
@@ -280,5 +340,7 @@ pub async fn store_validators(
         info!("Stored {} new validator records", insertions);
     }
 
+    notify(psql_client, notifications_config, events).await?;
+
     Ok(())
 }
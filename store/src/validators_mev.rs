@@ -18,14 +18,19 @@ pub struct StoreMevOptions {
 
 const DEFAULT_CHUNK_SIZE: usize = 500;
 
-pub async fn store_mev(
-    options: StoreMevOptions,
+pub async fn store_mev(options: StoreMevOptions, psql_client: &mut Client) -> anyhow::Result<()> {
+    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
+    let snapshot: Snapshot = serde_yaml::from_reader(snapshot_file)?;
+
+    store_mev_snapshot(&snapshot, psql_client).await
+}
+
+pub async fn store_mev_snapshot(
+    snapshot: &Snapshot,
     mut psql_client: &mut Client,
 ) -> anyhow::Result<()> {
     info!("Storing MEV snapshot...");
 
-    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
-    let snapshot: Snapshot = serde_yaml::from_reader(snapshot_file)?;
     let snapshot_created_at = snapshot.created_at.parse::<DateTime<Utc>>().unwrap();
 
     let validators_mev: HashMap<_, _> = snapshot
@@ -4,7 +4,9 @@ use collect::validators_performance::ValidatorsPerformanceSnapshot;
 use log::info;
 use rust_decimal::prelude::*;
 use serde_yaml;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use store::dto::{NotificationsConfig, ValidatorEvent, ValidatorEventType};
+use store::notifications::{load_notifications_config, notify};
 use structopt::StructOpt;
 use tokio_postgres::{types::ToSql, Client};
 
@@ -12,16 +14,35 @@ use tokio_postgres::{types::ToSql, Client};
 pub struct StoreVersionsOptions {
     #[structopt(long = "snapshot-file")]
     snapshot_path: String,
+
+    #[structopt(
+        long = "notifications-config",
+        help = "Validator events are sent to the sinks configured in this file"
+    )]
+    notifications_config_path: Option<String>,
 }
 
 pub async fn store_versions(
     options: StoreVersionsOptions,
+    psql_client: &mut Client,
+) -> anyhow::Result<()> {
+    let notifications_config = match &options.notifications_config_path {
+        Some(path) => load_notifications_config(path)?,
+        None => Default::default(),
+    };
+    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
+    let snapshot: ValidatorsPerformanceSnapshot = serde_yaml::from_reader(snapshot_file)?;
+
+    store_versions_snapshot(&snapshot, psql_client, &notifications_config).await
+}
+
+pub async fn store_versions_snapshot(
+    snapshot: &ValidatorsPerformanceSnapshot,
     mut psql_client: &mut Client,
+    notifications_config: &NotificationsConfig,
 ) -> anyhow::Result<()> {
     info!("Storing versions...");
 
-    let snapshot_file = std::fs::File::open(options.snapshot_path)?;
-    let snapshot: ValidatorsPerformanceSnapshot = serde_yaml::from_reader(snapshot_file)?;
     let snapshot_epoch_slot: Decimal = snapshot.epoch_slot.into();
     let snapshot_epoch: Decimal = snapshot.epoch.into();
     let snapshot_created_at = snapshot.created_at.parse::<DateTime<Utc>>().unwrap();
@@ -29,6 +50,7 @@ pub async fn store_versions(
     info!("Loaded the snapshot");
 
     let mut skipped_vote_accounts: HashSet<String> = Default::default();
+    let mut previous_versions: HashMap<String, Option<String>> = Default::default();
 
     for row in psql_client
         .query(
@@ -53,6 +75,7 @@ pub async fn store_versions(
                 skipped_vote_accounts.insert(vote_account.to_string());
             }
         }
+        previous_versions.insert(vote_account.to_string(), version);
     }
 
     let mut query = InsertQueryCombiner::new(
@@ -60,7 +83,20 @@ pub async fn store_versions(
         "vote_account, version, epoch_slot, epoch, created_at".to_string(),
     );
 
+    let mut events: Vec<ValidatorEvent> = Default::default();
     for (vote_account, v) in snapshot.validators.iter() {
+        if let Some(previous_version) = previous_versions.get(vote_account) {
+            if previous_version != &v.version {
+                events.push(ValidatorEvent {
+                    vote_account: vote_account.clone(),
+                    event_type: ValidatorEventType::VersionChange,
+                    epoch: snapshot.epoch,
+                    value_before: previous_version.clone(),
+                    value_after: v.version.clone(),
+                    created_at: snapshot_created_at,
+                });
+            }
+        }
         if !skipped_vote_accounts.contains(vote_account) {
             let mut params: Vec<&(dyn ToSql + Sync)> = vec![
                 vote_account,
@@ -76,5 +112,7 @@ pub async fn store_versions(
 
     info!("Stored {} version changes", insertions.unwrap_or(0));
 
+    notify(psql_client, notifications_config, events).await?;
+
     Ok(())
 }